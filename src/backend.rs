@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{collections::HashSet, fmt::Display};
 
 use space::Space;
 
@@ -13,33 +13,33 @@ pub use position::*;
 
 use crate::graph;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Default)]
 pub enum SpaceCell {
+    #[default]
     Empty,
     Reserved,
     Used(SpaceBlock),
 }
 
-impl Default for SpaceCell {
-    fn default() -> Self {
-        Self::Empty
-    }
-}
-
 #[derive(Debug, PartialEq, Clone)]
 pub enum SpaceBlock {
     SolidBlock,
     Redstone,
+    RedstoneBlock,
     Comparator {
         direction: Orientation,
         activated: bool,
     },
+    /// A Repeater with the given Delay in Ticks, between 1 and 4
     Repeater {
         direction: Orientation,
+        delay: u8,
     },
     TorchOnBlock {
         direction: Orientation,
     },
+    /// A Torch standing on top of the Block below it
+    Torch,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -72,6 +72,9 @@ pub enum PlacedNodeData {
     Variable {
         name: String,
     },
+    Constant {
+        value: bool,
+    },
     Splitter {
         input: (usize, usize, usize),
         ports: Vec<(usize, usize, usize)>,
@@ -98,6 +101,17 @@ pub fn generate_layout(graph: graph::normalized::Graph) -> Layout {
     let mut placed_nodes = Vec::new();
     let mut nodes_to_place = graph.nodes_with_predecessors();
 
+    // Loops are placed as if the Edges closing them did not exist, those Edges are then simply
+    // routed backwards
+    let feedback: HashSet<(u32, u32)> = graph
+        .feedback_edges()
+        .into_iter()
+        .map(|e| (e.src_id, e.dest_id))
+        .collect();
+    for (node, preds) in nodes_to_place.iter_mut() {
+        preds.retain(|p| !feedback.contains(&(p.id, node.id)));
+    }
+
     let mut x_offset = 1;
     let z = 8;
     while !nodes_to_place.is_empty() {
@@ -147,11 +161,14 @@ pub fn generate_layout(graph: graph::normalized::Graph) -> Layout {
 pub enum BlockData {
     Stone,
     Redstone,
+    RedstoneBlock,
     TorchOnBlock {
         orient: Orientation,
     },
+    Torch,
     Repeater {
         orient: Orientation,
+        delay: u8,
     },
     Comparator {
         orient: Orientation,
@@ -185,8 +202,12 @@ impl Layout {
             .map(|(pos, content)| {
                 let data = match content {
                     SpaceBlock::Redstone => BlockData::Redstone,
+                    SpaceBlock::RedstoneBlock => BlockData::RedstoneBlock,
                     SpaceBlock::SolidBlock => BlockData::Stone,
-                    SpaceBlock::Repeater { direction } => BlockData::Repeater { orient: direction },
+                    SpaceBlock::Repeater { direction, delay } => BlockData::Repeater {
+                        orient: direction,
+                        delay,
+                    },
                     SpaceBlock::Comparator {
                         direction,
                         activated,
@@ -197,6 +218,7 @@ impl Layout {
                     SpaceBlock::TorchOnBlock { direction } => {
                         BlockData::TorchOnBlock { orient: direction }
                     }
+                    SpaceBlock::Torch => BlockData::Torch,
                 };
 
                 MinecraftBlock {
@@ -218,8 +240,13 @@ impl MinecraftBlock {
         let block_str = match &self.data {
             BlockData::Stone => "stone".to_string(),
             BlockData::Redstone => "redstone_wire".to_string(),
+            BlockData::RedstoneBlock => "redstone_block".to_string(),
             BlockData::TorchOnBlock { orient } => format!("redstone_wall_torch[facing={}]", orient),
-            BlockData::Repeater { orient } => format!("repeater[facing={}]", orient),
+            BlockData::Torch => "redstone_torch".to_string(),
+            BlockData::Repeater { orient, delay: 1 } => format!("repeater[facing={}]", orient),
+            BlockData::Repeater { orient, delay } => {
+                format!("repeater[facing={},delay={}]", orient, delay)
+            }
             BlockData::Comparator { orient, activated } if *activated => {
                 format!("comparator[facing={},mode=subtract]", orient)
             }
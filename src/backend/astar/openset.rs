@@ -36,24 +36,21 @@ where
     }
 
     pub fn update(&mut self, item: C, cost: i64) {
-        match self.values.get(&item) {
-            Some(v) => {
-                let prev_cost = *v;
-
-                let prev_list = self.inner.get_mut(&prev_cost).unwrap();
-                let index = prev_list
-                    .iter()
-                    .enumerate()
-                    .find(|(_, c)| c == &&item)
-                    .map(|(i, _)| i)
-                    .unwrap();
-
-                prev_list.remove(index);
-            }
-            None => {}
-        };
-
-        let list = self.inner.entry(cost).or_insert(Vec::new());
+        if let Some(v) = self.values.get(&item) {
+            let prev_cost = *v;
+
+            let prev_list = self.inner.get_mut(&prev_cost).unwrap();
+            let index = prev_list
+                .iter()
+                .enumerate()
+                .find(|(_, c)| c == &&item)
+                .map(|(i, _)| i)
+                .unwrap();
+
+            prev_list.remove(index);
+        }
+
+        let list = self.inner.entry(cost).or_default();
         if !list.contains(&item) {
             list.push(item);
         }
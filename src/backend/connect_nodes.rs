@@ -27,6 +27,7 @@ fn place_edge(space: &mut Space<SpaceCell>, edge: &graph::normalized::Edge, node
         PlacedNodeData::Input { .. } => src_node.0,
         PlacedNodeData::Output { .. } => src_node.0,
         PlacedNodeData::Variable { .. } => src_node.0,
+        PlacedNodeData::Constant { .. } => src_node.0,
         PlacedNodeData::Splitter { ports, .. } => *ports.get(edge.src_port as usize).unwrap(),
         PlacedNodeData::Entity { out_ports, .. } => *out_ports.get(edge.src_port as usize).unwrap(),
     };
@@ -40,6 +41,7 @@ fn place_edge(space: &mut Space<SpaceCell>, edge: &graph::normalized::Edge, node
         PlacedNodeData::Input { .. } => dest_node.0,
         PlacedNodeData::Output { .. } => dest_node.0,
         PlacedNodeData::Variable { .. } => dest_node.0,
+        PlacedNodeData::Constant { .. } => unreachable!("Constants have no Inputs"),
         PlacedNodeData::Splitter { input, .. } => *input,
         PlacedNodeData::Entity { in_ports, .. } => *in_ports.get(edge.dest_port as usize).unwrap(),
     };
@@ -65,11 +67,9 @@ fn place_edge(space: &mut Space<SpaceCell>, edge: &graph::normalized::Edge, node
         search_s_pos,
         search_d_pos,
         |(src_x, src_y, src_z), (dest_x, dest_y, dest_z)| {
-            let result = (dest_x as i64 - src_x as i64).abs()
+            (dest_x as i64 - src_x as i64).abs()
                 + (dest_y as i64 - src_y as i64).abs()
-                + (dest_z as i64 - src_z as i64).abs();
-
-            result as i64
+                + (dest_z as i64 - src_z as i64).abs()
         },
         |s, pos| neighbours(s, pos, search_d_pos),
     );
@@ -94,11 +94,14 @@ fn neighbours(
         .filter(|((x, y, z), _)| {
             matches!(s.get((*x, *y, z + 1)), SpaceCell::Empty) || (*x, *y, *z) == dest
         })
+        // Checks if the Block above is free, which it always is at the Top of the Space
         .filter(|((x, y, z), _)| {
-            matches!(
-                s.get((*x, *y, z - 1)),
-                SpaceCell::Empty | SpaceCell::Reserved
-            ) || (*x, *y, *z) == dest
+            z.checked_sub(1).is_none_or(|above| {
+                matches!(
+                    s.get((*x, *y, above)),
+                    SpaceCell::Empty | SpaceCell::Reserved
+                )
+            }) || (*x, *y, *z) == dest
         })
         .collect()
 }
@@ -109,7 +112,7 @@ fn base_neighbours(
 ) -> impl Iterator<Item = ((usize, usize, usize), i64)> {
     let base_cords = [
         (pos.0 + 1, pos.1, pos.2),
-        (pos.0 - 1, pos.1, pos.2),
+        (pos.0.saturating_sub(1), pos.1, pos.2),
         (pos.0, pos.1 + 1, pos.2),
         (pos.0, pos.1.saturating_sub(1), pos.2),
     ];
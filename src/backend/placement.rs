@@ -3,7 +3,9 @@ use crate::{
     graph,
 };
 
+mod matrix;
 mod reserve;
+use matrix::{place_ram, place_rom};
 use reserve::reserve_around;
 
 pub fn place_node(
@@ -44,6 +46,77 @@ pub fn place_node(
             reserve_around(space, (x_offset, y_offset, z_pos), (1, 1, 1), RESERVE_SPACE);
             ((1, 1, 1), PlacedNodeData::Variable { name })
         }
+        // An always active Constant is a Redstone-Block powering the Wire connected to it, while
+        // an inactive Constant is simply a Wire without any Source
+        graph::normalized::NodeType::Constant { value: true } => {
+            space.set((x_offset, y_offset, z_pos), |_| {
+                SpaceCell::Used(SpaceBlock::RedstoneBlock)
+            });
+            reserve_around(space, (x_offset, y_offset, z_pos), (1, 1, 1), RESERVE_SPACE);
+            ((1, 1, 1), PlacedNodeData::Constant { value: true })
+        }
+        graph::normalized::NodeType::Constant { value: false } => {
+            space.set((x_offset, y_offset, z_pos), |_| {
+                SpaceCell::Used(SpaceBlock::Redstone)
+            });
+            space.set((x_offset, y_offset, z_pos + 1), |_| {
+                SpaceCell::Used(SpaceBlock::SolidBlock)
+            });
+            reserve_around(space, (x_offset, y_offset, z_pos), (1, 1, 1), RESERVE_SPACE);
+            ((1, 1, 1), PlacedNodeData::Constant { value: false })
+        }
+        // A Torch inverting its own Output after it went through a Loop of Repeaters, so the
+        // Torch and the Repeaters together make up half of the Period
+        graph::normalized::NodeType::Clock { period } => {
+            let delays = repeater_delays(period / 2 - 1);
+            let end = delays.len() + 2;
+
+            let redstone_pos = (0..=end)
+                .map(|x| (x_offset + x, y_offset + 2, z_pos))
+                .chain([
+                    (x_offset + end, y_offset, z_pos),
+                    (x_offset + end, y_offset + 1, z_pos),
+                    (x_offset, y_offset + 1, z_pos),
+                ]);
+
+            for pos in redstone_pos {
+                space.set(pos, |_| SpaceCell::Used(SpaceBlock::Redstone));
+                space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                    SpaceCell::Used(SpaceBlock::SolidBlock)
+                });
+            }
+            for (index, delay) in delays.into_iter().enumerate() {
+                let pos = (x_offset + 2 + index, y_offset, z_pos);
+                space.set(pos, |_| {
+                    SpaceCell::Used(SpaceBlock::Repeater {
+                        direction: Orientation::East,
+                        delay,
+                    })
+                });
+                space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                    SpaceCell::Used(SpaceBlock::SolidBlock)
+                });
+            }
+            space.set((x_offset, y_offset, z_pos), |_| {
+                SpaceCell::Used(SpaceBlock::SolidBlock)
+            });
+            space.set((x_offset + 1, y_offset, z_pos), |_| {
+                SpaceCell::Used(SpaceBlock::TorchOnBlock {
+                    direction: Orientation::West,
+                })
+            });
+
+            let size = (end + 1, 3, 1);
+            reserve_around(space, (x_offset, y_offset, z_pos), size, RESERVE_SPACE);
+
+            (
+                size,
+                PlacedNodeData::Entity {
+                    in_ports: Vec::new(),
+                    out_ports: [(x_offset + end, y_offset, z_pos)].to_vec(),
+                },
+            )
+        }
         graph::normalized::NodeType::Splitter { port_count: ports } => {
             let height = 1 + 2 * ((ports as usize) - 1);
             let input_height = (height - 1) / 2;
@@ -119,6 +192,7 @@ pub fn place_node(
                     space.set(pos, |_| {
                         SpaceCell::Used(SpaceBlock::Repeater {
                             direction: Orientation::East,
+                            delay: 1,
                         })
                     });
                     space.set((pos.0, pos.1, pos.2 + 1), |_| {
@@ -180,6 +254,7 @@ pub fn place_node(
                     space.set(pos, |_| {
                         SpaceCell::Used(SpaceBlock::Repeater {
                             direction: Orientation::East,
+                            delay: 1,
                         })
                     });
                     space.set((pos.0, pos.1, pos.2 + 1), |_| {
@@ -236,7 +311,491 @@ pub fn place_node(
                     },
                 )
             }
-            other => todo!("Handle: {:?}", other),
+            // Both Inputs power their own Block, where the Torches on them are only on if the
+            // Block is not powered and either Torch powers the Output
+            graph::normalized::BuiltinOp::Nand => {
+                let redstone_pos = [
+                    (x_offset, y_offset, z_pos),
+                    (x_offset, y_offset + 2, z_pos),
+                    (x_offset + 3, y_offset + 1, z_pos),
+                    (x_offset + 4, y_offset + 1, z_pos),
+                ];
+                let repeater_pos = [
+                    (x_offset + 1, y_offset, z_pos),
+                    (x_offset + 1, y_offset + 2, z_pos),
+                ];
+                let torch_pos = [
+                    (x_offset + 3, y_offset, z_pos),
+                    (x_offset + 3, y_offset + 2, z_pos),
+                ];
+                let solid_pos = [
+                    (x_offset + 2, y_offset, z_pos),
+                    (x_offset + 2, y_offset + 2, z_pos),
+                ];
+
+                for pos in redstone_pos {
+                    space.set(pos, |_| SpaceCell::Used(SpaceBlock::Redstone));
+                    space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+                for pos in repeater_pos {
+                    space.set(pos, |_| {
+                        SpaceCell::Used(SpaceBlock::Repeater {
+                            direction: Orientation::East,
+                            delay: 1,
+                        })
+                    });
+                    space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+                for pos in torch_pos {
+                    space.set(pos, |_| {
+                        SpaceCell::Used(SpaceBlock::TorchOnBlock {
+                            direction: Orientation::West,
+                        })
+                    });
+                }
+                for pos in solid_pos {
+                    space.set(pos, |_| SpaceCell::Used(SpaceBlock::SolidBlock));
+                }
+
+                reserve_around(space, (x_offset, y_offset, z_pos), (5, 3, 1), RESERVE_SPACE);
+
+                (
+                    (5, 3, 1),
+                    PlacedNodeData::Entity {
+                        in_ports: [(x_offset, y_offset, z_pos), (x_offset, y_offset + 2, z_pos)]
+                            .to_vec(),
+                        out_ports: [(x_offset + 4, y_offset + 1, z_pos)].to_vec(),
+                    },
+                )
+            }
+            // Both Inputs power the same Block, so the Torch on it is only on if neither of them
+            // is on
+            graph::normalized::BuiltinOp::Nor => {
+                let redstone_pos = [
+                    (x_offset, y_offset, z_pos),
+                    (x_offset, y_offset + 2, z_pos),
+                    (x_offset + 1, y_offset, z_pos),
+                    (x_offset + 1, y_offset + 1, z_pos),
+                    (x_offset + 1, y_offset + 2, z_pos),
+                    (x_offset + 2, y_offset + 1, z_pos),
+                ];
+
+                for pos in redstone_pos {
+                    space.set(pos, |_| SpaceCell::Used(SpaceBlock::Redstone));
+                    space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+                space.set((x_offset + 3, y_offset + 1, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::SolidBlock)
+                });
+                space.set((x_offset + 4, y_offset + 1, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::TorchOnBlock {
+                        direction: Orientation::West,
+                    })
+                });
+
+                reserve_around(space, (x_offset, y_offset, z_pos), (5, 3, 1), RESERVE_SPACE);
+
+                (
+                    (5, 3, 1),
+                    PlacedNodeData::Entity {
+                        in_ports: [(x_offset, y_offset, z_pos), (x_offset, y_offset + 2, z_pos)]
+                            .to_vec(),
+                        out_ports: [(x_offset + 4, y_offset + 1, z_pos)].to_vec(),
+                    },
+                )
+            }
+            // The same as the Xor, but the Output powers a Block with a Torch on it
+            graph::normalized::BuiltinOp::Xnor => {
+                let redstone_pos = [
+                    (x_offset, y_offset, z_pos),
+                    (x_offset, y_offset + 3, z_pos),
+                    (x_offset + 3, y_offset, z_pos),
+                    (x_offset + 3, y_offset + 3, z_pos),
+                    (x_offset + 4, y_offset, z_pos),
+                    (x_offset + 4, y_offset + 1, z_pos),
+                    (x_offset + 4, y_offset + 2, z_pos),
+                    (x_offset + 4, y_offset + 3, z_pos),
+                    (x_offset + 5, y_offset + 1, z_pos),
+                    (x_offset + 6, y_offset + 1, z_pos),
+                ];
+                let solid_pos = [
+                    (x_offset + 2, y_offset, z_pos),
+                    (x_offset + 2, y_offset + 1, z_pos),
+                    (x_offset + 2, y_offset + 2, z_pos),
+                    (x_offset + 2, y_offset + 3, z_pos),
+                    (x_offset + 7, y_offset + 1, z_pos),
+                ];
+                let repeater_pos = [
+                    (x_offset + 1, y_offset, z_pos),
+                    (x_offset + 1, y_offset + 3, z_pos),
+                ];
+                let comparator_pos = [
+                    (x_offset + 3, y_offset + 1, z_pos),
+                    (x_offset + 3, y_offset + 2, z_pos),
+                ];
+
+                for pos in redstone_pos {
+                    space.set(pos, |_| SpaceCell::Used(SpaceBlock::Redstone));
+                    space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+                for pos in solid_pos {
+                    space.set(pos, |_| SpaceCell::Used(SpaceBlock::SolidBlock));
+                }
+                for pos in repeater_pos {
+                    space.set(pos, |_| {
+                        SpaceCell::Used(SpaceBlock::Repeater {
+                            direction: Orientation::East,
+                            delay: 1,
+                        })
+                    });
+                    space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+                for pos in comparator_pos {
+                    space.set(pos, |_| {
+                        SpaceCell::Used(SpaceBlock::Comparator {
+                            direction: Orientation::East,
+                            activated: true,
+                        })
+                    });
+                    space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+                space.set((x_offset + 8, y_offset + 1, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::TorchOnBlock {
+                        direction: Orientation::West,
+                    })
+                });
+
+                reserve_around(space, (x_offset, y_offset, z_pos), (9, 4, 1), RESERVE_SPACE);
+
+                (
+                    (9, 4, 1),
+                    PlacedNodeData::Entity {
+                        in_ports: [(x_offset, y_offset, z_pos), (x_offset, y_offset + 3, z_pos)]
+                            .to_vec(),
+                        out_ports: [(x_offset + 8, y_offset + 1, z_pos)].to_vec(),
+                    },
+                )
+            }
+            // The Input powers a Block, which turns off the Torch on it
+            graph::normalized::BuiltinOp::Not => {
+                let redstone_pos = [(x_offset, y_offset, z_pos), (x_offset + 1, y_offset, z_pos)];
+
+                for pos in redstone_pos {
+                    space.set(pos, |_| SpaceCell::Used(SpaceBlock::Redstone));
+                    space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+                space.set((x_offset + 2, y_offset, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::SolidBlock)
+                });
+                space.set((x_offset + 3, y_offset, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::TorchOnBlock {
+                        direction: Orientation::West,
+                    })
+                });
+
+                reserve_around(space, (x_offset, y_offset, z_pos), (4, 1, 1), RESERVE_SPACE);
+
+                (
+                    (4, 1, 1),
+                    PlacedNodeData::Entity {
+                        in_ports: [(x_offset, y_offset, z_pos)].to_vec(),
+                        out_ports: [(x_offset + 3, y_offset, z_pos)].to_vec(),
+                    },
+                )
+            }
+            // A Repeater passes the Data on, as long as it is not locked by a second Repeater
+            // powered from its Side. The Torch on the Enable-Block keeps it locked while the
+            // Enable-Input is off
+            graph::normalized::BuiltinOp::Latch => {
+                let redstone_pos = [
+                    (x_offset, y_offset, z_pos),
+                    (x_offset + 1, y_offset, z_pos),
+                    (x_offset + 3, y_offset, z_pos),
+                    (x_offset, y_offset + 3, z_pos),
+                    (x_offset + 1, y_offset + 3, z_pos),
+                ];
+
+                for pos in redstone_pos {
+                    space.set(pos, |_| SpaceCell::Used(SpaceBlock::Redstone));
+                    space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+                space.set((x_offset + 2, y_offset + 3, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::SolidBlock)
+                });
+                space.set((x_offset + 2, y_offset + 2, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::TorchOnBlock {
+                        direction: Orientation::South,
+                    })
+                });
+                space.set((x_offset + 2, y_offset + 1, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::Repeater {
+                        direction: Orientation::North,
+                        delay: 1,
+                    })
+                });
+                space.set((x_offset + 2, y_offset, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::Repeater {
+                        direction: Orientation::East,
+                        delay: 1,
+                    })
+                });
+                for pos in [(x_offset + 2, y_offset), (x_offset + 2, y_offset + 1)] {
+                    space.set((pos.0, pos.1, z_pos + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+
+                reserve_around(space, (x_offset, y_offset, z_pos), (4, 4, 1), RESERVE_SPACE);
+
+                (
+                    (4, 4, 1),
+                    PlacedNodeData::Entity {
+                        in_ports: [(x_offset, y_offset, z_pos), (x_offset, y_offset + 3, z_pos)]
+                            .to_vec(),
+                        out_ports: [(x_offset + 3, y_offset, z_pos)].to_vec(),
+                    },
+                )
+            }
+            // Two Latches in a row, the first one is open while the Clock is off and the second
+            // one while the Clock is on, so the Value only moves through on the rising Edge
+            graph::normalized::BuiltinOp::Dff => {
+                let redstone_pos = [
+                    (x_offset, y_offset, z_pos),
+                    (x_offset + 1, y_offset, z_pos),
+                    (x_offset + 3, y_offset, z_pos),
+                    (x_offset + 5, y_offset, z_pos),
+                    (x_offset + 2, y_offset + 2, z_pos),
+                    (x_offset, y_offset + 3, z_pos),
+                    (x_offset + 1, y_offset + 3, z_pos),
+                    (x_offset + 2, y_offset + 3, z_pos),
+                    (x_offset + 3, y_offset + 3, z_pos),
+                ];
+                let data_repeater_pos = [
+                    (x_offset + 2, y_offset, z_pos),
+                    (x_offset + 4, y_offset, z_pos),
+                ];
+                let lock_repeater_pos = [
+                    (x_offset + 2, y_offset + 1, z_pos),
+                    (x_offset + 4, y_offset + 1, z_pos),
+                ];
+
+                for pos in redstone_pos {
+                    space.set(pos, |_| SpaceCell::Used(SpaceBlock::Redstone));
+                    space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+                for pos in data_repeater_pos {
+                    space.set(pos, |_| {
+                        SpaceCell::Used(SpaceBlock::Repeater {
+                            direction: Orientation::East,
+                            delay: 1,
+                        })
+                    });
+                    space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+                for pos in lock_repeater_pos {
+                    space.set(pos, |_| {
+                        SpaceCell::Used(SpaceBlock::Repeater {
+                            direction: Orientation::North,
+                            delay: 1,
+                        })
+                    });
+                    space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+                space.set((x_offset + 4, y_offset + 3, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::SolidBlock)
+                });
+                space.set((x_offset + 4, y_offset + 2, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::TorchOnBlock {
+                        direction: Orientation::South,
+                    })
+                });
+
+                reserve_around(space, (x_offset, y_offset, z_pos), (6, 4, 1), RESERVE_SPACE);
+
+                (
+                    (6, 4, 1),
+                    PlacedNodeData::Entity {
+                        in_ports: [(x_offset, y_offset, z_pos), (x_offset, y_offset + 3, z_pos)]
+                            .to_vec(),
+                        out_ports: [(x_offset + 5, y_offset, z_pos)].to_vec(),
+                    },
+                )
+            }
+            // The Latch from above, which stores the Set-Input whenever Set or Reset is active.
+            // Set is fed into the Enable-Line through a Repeater, so Reset can't flow back into it
+            graph::normalized::BuiltinOp::RsLatch => {
+                let redstone_pos = [
+                    (x_offset, y_offset, z_pos),
+                    (x_offset + 1, y_offset, z_pos),
+                    (x_offset + 3, y_offset, z_pos),
+                    (x_offset, y_offset + 1, z_pos),
+                    (x_offset, y_offset + 3, z_pos),
+                    (x_offset + 1, y_offset + 3, z_pos),
+                ];
+
+                for pos in redstone_pos {
+                    space.set(pos, |_| SpaceCell::Used(SpaceBlock::Redstone));
+                    space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+                space.set((x_offset, y_offset + 2, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::Repeater {
+                        direction: Orientation::South,
+                        delay: 1,
+                    })
+                });
+                space.set((x_offset + 2, y_offset + 3, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::SolidBlock)
+                });
+                space.set((x_offset + 2, y_offset + 2, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::TorchOnBlock {
+                        direction: Orientation::South,
+                    })
+                });
+                space.set((x_offset + 2, y_offset + 1, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::Repeater {
+                        direction: Orientation::North,
+                        delay: 1,
+                    })
+                });
+                space.set((x_offset + 2, y_offset, z_pos), |_| {
+                    SpaceCell::Used(SpaceBlock::Repeater {
+                        direction: Orientation::East,
+                        delay: 1,
+                    })
+                });
+                for pos in [
+                    (x_offset, y_offset + 2),
+                    (x_offset + 2, y_offset),
+                    (x_offset + 2, y_offset + 1),
+                ] {
+                    space.set((pos.0, pos.1, z_pos + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+
+                reserve_around(space, (x_offset, y_offset, z_pos), (4, 4, 1), RESERVE_SPACE);
+
+                (
+                    (4, 4, 1),
+                    PlacedNodeData::Entity {
+                        in_ports: [(x_offset, y_offset, z_pos), (x_offset, y_offset + 3, z_pos)]
+                            .to_vec(),
+                        out_ports: [(x_offset + 3, y_offset, z_pos)].to_vec(),
+                    },
+                )
+            }
+            // The Input and the inverted, delayed Input are combined by a Nor, which is only on
+            // between the Input turning on and the delayed Input catching up
+            graph::normalized::BuiltinOp::Pulse { length } => {
+                // The Torch on the direct Path already takes one of the Ticks
+                let delays = repeater_delays(length + 1);
+                let join = std::cmp::max(4, delays.len() + 2);
+
+                let redstone_pos = [
+                    (x_offset, y_offset, z_pos),
+                    (x_offset + 1, y_offset, z_pos),
+                    (x_offset, y_offset + 1, z_pos),
+                    (x_offset, y_offset + 2, z_pos),
+                    (x_offset + join, y_offset + 1, z_pos),
+                    (x_offset + join + 1, y_offset + 1, z_pos),
+                ]
+                .into_iter()
+                .chain((4..=join).map(|x| (x_offset + x, y_offset, z_pos)))
+                .chain((delays.len() + 1..=join).map(|x| (x_offset + x, y_offset + 2, z_pos)));
+
+                for pos in redstone_pos {
+                    space.set(pos, |_| SpaceCell::Used(SpaceBlock::Redstone));
+                    space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+                for (index, delay) in delays.into_iter().enumerate() {
+                    let pos = (x_offset + 1 + index, y_offset + 2, z_pos);
+                    space.set(pos, |_| {
+                        SpaceCell::Used(SpaceBlock::Repeater {
+                            direction: Orientation::East,
+                            delay,
+                        })
+                    });
+                    space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                }
+                for (block, torch) in [((2, 0), (3, 0)), ((join + 2, 1), (join + 3, 1))] {
+                    space.set((x_offset + block.0, y_offset + block.1, z_pos), |_| {
+                        SpaceCell::Used(SpaceBlock::SolidBlock)
+                    });
+                    space.set((x_offset + torch.0, y_offset + torch.1, z_pos), |_| {
+                        SpaceCell::Used(SpaceBlock::TorchOnBlock {
+                            direction: Orientation::West,
+                        })
+                    });
+                }
+
+                let size = (join + 4, 3, 1);
+                reserve_around(space, (x_offset, y_offset, z_pos), size, RESERVE_SPACE);
+
+                (
+                    size,
+                    PlacedNodeData::Entity {
+                        in_ports: [(x_offset, y_offset, z_pos)].to_vec(),
+                        out_ports: [(x_offset + join + 3, y_offset + 1, z_pos)].to_vec(),
+                    },
+                )
+            }
+            graph::normalized::BuiltinOp::Rom {
+                address_width,
+                data_width,
+                words,
+            } => place_rom(
+                space,
+                (x_offset, y_offset, z_pos),
+                address_width as usize,
+                data_width as usize,
+                &words,
+            ),
+            graph::normalized::BuiltinOp::Ram {
+                address_width,
+                data_width,
+            } => place_ram(
+                space,
+                (x_offset, y_offset, z_pos),
+                address_width as usize,
+                data_width as usize,
+            ),
         },
     }
 }
+
+/// Splits the Delay into the Delays of as few Repeaters as possible, each between 1 and 4 Ticks
+fn repeater_delays(total: u32) -> Vec<u8> {
+    let mut delays = vec![4; (total / 4) as usize];
+    if !total.is_multiple_of(4) {
+        delays.push((total % 4) as u8);
+    }
+    delays
+}
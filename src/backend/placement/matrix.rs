@@ -0,0 +1,360 @@
+use crate::backend::{space, Orientation, PlacedNodeData, SpaceBlock, SpaceCell, RESERVE_SPACE};
+
+use super::reserve::reserve_around;
+
+type Position = (usize, usize, usize);
+
+/// The Blocks of a Matrix, with Positions relative to its Origin, except for the Height
+#[derive(Default)]
+struct Blocks {
+    redstone: Vec<Position>,
+    repeaters: Vec<(Position, Orientation)>,
+    solid: Vec<Position>,
+    wall_torches: Vec<(Position, Orientation)>,
+    /// Blocks with a Torch standing on top of them
+    cells: Vec<Position>,
+}
+
+impl Blocks {
+    fn place(self, space: &mut space::Space<SpaceCell>, (x_offset, y_offset): (usize, usize)) {
+        let offset = |(x, y, z): Position| (x_offset + x, y_offset + y, z);
+
+        for pos in self.redstone.into_iter().map(offset) {
+            space.set(pos, |_| SpaceCell::Used(SpaceBlock::Redstone));
+            space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                SpaceCell::Used(SpaceBlock::SolidBlock)
+            });
+        }
+        for (pos, direction) in self.repeaters {
+            let pos = offset(pos);
+            space.set(pos, |_| {
+                SpaceCell::Used(SpaceBlock::Repeater {
+                    direction: direction.clone(),
+                    delay: 1,
+                })
+            });
+            space.set((pos.0, pos.1, pos.2 + 1), |_| {
+                SpaceCell::Used(SpaceBlock::SolidBlock)
+            });
+        }
+        for pos in self.solid.into_iter().map(offset) {
+            space.set(pos, |_| SpaceCell::Used(SpaceBlock::SolidBlock));
+        }
+        for (pos, direction) in self.wall_torches {
+            space.set(offset(pos), |_| {
+                SpaceCell::Used(SpaceBlock::TorchOnBlock {
+                    direction: direction.clone(),
+                })
+            });
+        }
+        for pos in self.cells.into_iter().map(offset) {
+            space.set(pos, |_| SpaceCell::Used(SpaceBlock::SolidBlock));
+            space.set((pos.0, pos.1, pos.2 - 1), |_| {
+                SpaceCell::Used(SpaceBlock::Torch)
+            });
+        }
+    }
+}
+
+/// Places the Inputs of the Address at `(0, 4 * bit)`, which turn into Address-Lines alternating
+/// between every Bit of the Address and its Inverse, so Line `2 * bit` is the Bit itself and Line
+/// `2 * bit + 1` its Inverse. The Lines continue at `x = 2`
+fn address_inputs(blocks: &mut Blocks, address_width: usize, z_pos: usize) {
+    for bit in 0..address_width {
+        blocks.redstone.extend([
+            (0, 4 * bit, z_pos),
+            (1, 4 * bit, z_pos),
+            (0, 4 * bit + 1, z_pos),
+        ]);
+        blocks.solid.push((0, 4 * bit + 2, z_pos));
+        blocks
+            .wall_torches
+            .push(((1, 4 * bit + 2, z_pos), Orientation::West));
+    }
+}
+
+/// The Address-Lines that need to be on for the given Address to be selected
+fn address_lines(address: usize, address_width: usize) -> Vec<usize> {
+    (0..address_width)
+        .map(|bit| {
+            if address & (1 << bit) != 0 {
+                2 * bit
+            } else {
+                2 * bit + 1
+            }
+        })
+        .collect()
+}
+
+/// Places a Decoder, whose Lines run from `x = 2` along every second Row of the lower Layer and
+/// whose Columns run three Blocks above them at the given Positions.
+///
+/// A Cell of the Decoder replaces the Wire of the lower Layer with a Block that is powered by the
+/// Repeater in front of it and carries a Torch, which powers the upper Layer whenever the Line is
+/// off. Every Column has a Cell on each of its Lines, so it is off exactly when all of them are on
+fn decoder(blocks: &mut Blocks, line_count: usize, columns: &[(usize, Vec<usize>)], z_pos: usize) {
+    let top = z_pos - 3;
+    let last = columns.iter().map(|(x, _)| *x).max().unwrap_or(1);
+
+    for line in 0..line_count {
+        let y = 2 * line;
+        for x in 2..=last {
+            match columns.iter().find(|(column, _)| *column == x) {
+                Some((_, lines)) if lines.contains(&line) => blocks.cells.push((x, y, z_pos)),
+                Some(_) => blocks.redstone.push((x, y, z_pos)),
+                None if columns.iter().any(|(column, _)| *column == x + 1) => {
+                    blocks.repeaters.push(((x, y, z_pos), Orientation::East))
+                }
+                None => blocks.redstone.push((x, y, z_pos)),
+            }
+        }
+    }
+
+    for (x, _) in columns {
+        for line in 0..line_count {
+            blocks.redstone.push((*x, 2 * line, top));
+            blocks
+                .repeaters
+                .push(((*x, 2 * line + 1, top), Orientation::South));
+        }
+    }
+}
+
+/// Places a ROM as a Matrix, where every stored Word gets its own Column and every Address-Line
+/// and Data-Bit its own Row. The Rows run along the lower Layer, while the Columns run three
+/// Blocks above them, so they can cross each other.
+///
+/// The Columns start out in a Decoder, so they are off exactly when their Word is selected. In the
+/// Data-Region, the Columns run along the lower Layer instead and every set Bit of a Word is a
+/// Cell on the Row of that Bit, which is therefore on whenever the Word is selected.
+///
+/// Words that are 0 never turn on any Bit, so they don't need a Column at all
+pub fn place_rom(
+    space: &mut space::Space<SpaceCell>,
+    (x_offset, y_offset, z_pos): (usize, usize, usize),
+    address_width: usize,
+    data_width: usize,
+    words: &[u64],
+) -> ((usize, usize, usize), PlacedNodeData) {
+    let top = z_pos - 3;
+    let columns: Vec<_> = words
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| **word != 0)
+        .enumerate()
+        .map(|(index, (address, word))| (3 + 2 * index, address, *word))
+        .collect();
+    let end = 3 + 2 * columns.len();
+
+    let address_rows = 2 * address_width;
+    let data_start = 2 * address_rows + 4;
+    let data_row = |bit: usize| data_start + 1 + 2 * bit;
+
+    let mut blocks = Blocks::default();
+    address_inputs(&mut blocks, address_width, z_pos);
+    let decoder_columns: Vec<_> = columns
+        .iter()
+        .map(|(x, address, _)| (*x, address_lines(*address, address_width)))
+        .collect();
+    decoder(&mut blocks, address_rows, &decoder_columns, z_pos);
+
+    for (x, _, word) in columns.iter().copied() {
+        // The Column steps down to the lower Layer for the Data-Region
+        blocks
+            .redstone
+            .extend((0..4).map(|step| (x, 2 * address_rows + step, top + step)));
+
+        for bit in 0..data_width {
+            let y = data_row(bit);
+
+            blocks
+                .repeaters
+                .push(((x, y - 1, z_pos), Orientation::South));
+            if word & (1 << bit) != 0 {
+                blocks.cells.push((x, y, z_pos));
+            } else {
+                blocks.redstone.push((x, y, z_pos));
+            }
+
+            blocks.redstone.push((x, y, top));
+            blocks.repeaters.push(((x + 1, y, top), Orientation::East));
+        }
+    }
+
+    // The Data-Rows step down to the lower Layer for the Outputs
+    for bit in 0..data_width {
+        blocks
+            .redstone
+            .extend((0..4).map(|step| (end + step, data_row(bit), top + step)));
+    }
+
+    blocks.place(space, (x_offset, y_offset));
+
+    let size = (end + 4, data_row(data_width), 1);
+    reserve_around(space, (x_offset, y_offset, z_pos), size, RESERVE_SPACE);
+
+    (
+        size,
+        PlacedNodeData::Entity {
+            in_ports: (0..address_width)
+                .map(|bit| (x_offset, y_offset + 4 * bit, z_pos))
+                .collect(),
+            out_ports: (0..data_width)
+                .map(|bit| (x_offset + end + 3, y_offset + data_row(bit), z_pos))
+                .collect(),
+        },
+    )
+}
+
+/// The Width of the Tile storing a single Bit of a Word in a RAM
+const TILE_WIDTH: usize = 14;
+/// The Depth of the Tile storing a single Bit of a Word in a RAM
+const TILE_DEPTH: usize = 9;
+
+/// Places a RAM as a Grid of Tiles below a Decoder, where every Word gets its own Column of Tiles
+/// and every Data-Bit its own Row of them.
+///
+/// Every Word has two Columns of the Decoder, which are off while the Word is selected: the
+/// Write-Column at `x + 3` of its Tiles, which also needs the Write-Enable and Clock to be on, and
+/// the Read-Column at `x + 8`. A Tile consists of
+///
+/// * the Data-Input of the Bit along its first Row, branching off into a Latch, whose Repeater is
+///   locked by the Write-Column, so it only takes over the Data while the Word is written
+/// * the inverted Value of the Latch and the Read-Column, which power the same Block, so its Torch
+///   is only on if the Word is selected and the stored Bit is set
+/// * the Data-Output of the Bit along its last Row, which the Torch joins through a Repeater
+pub fn place_ram(
+    space: &mut space::Space<SpaceCell>,
+    (x_offset, y_offset, z_pos): (usize, usize, usize),
+    address_width: usize,
+    data_width: usize,
+) -> ((usize, usize, usize), PlacedNodeData) {
+    let top = z_pos - 3;
+    let words = 1 << address_width;
+    let end = TILE_WIDTH * words;
+
+    // The Write-Enable and Clock get their own Lines after the Address-Lines
+    let write_enable_line = 2 * address_width;
+    let clock_line = write_enable_line + 1;
+    let line_count = clock_line + 1;
+    let tile_row = |bit: usize| 2 * line_count + TILE_DEPTH * bit;
+
+    let mut blocks = Blocks::default();
+    address_inputs(&mut blocks, address_width, z_pos);
+    for line in [write_enable_line, clock_line] {
+        blocks
+            .redstone
+            .extend([(0, 2 * line, z_pos), (1, 2 * line, z_pos)]);
+    }
+
+    let columns: Vec<_> = (0..words)
+        .flat_map(|word| {
+            let x = TILE_WIDTH * word;
+            let read = address_lines(word, address_width);
+            let mut write = read.clone();
+            write.extend([write_enable_line, clock_line]);
+
+            [(x + 3, write), (x + 8, read)]
+        })
+        .collect();
+    decoder(&mut blocks, line_count, &columns, z_pos);
+
+    // The Columns continue through all the Tiles
+    for (x, _) in &columns {
+        for y in tile_row(0)..tile_row(data_width) {
+            if (y - tile_row(0)) % TILE_DEPTH == 1 {
+                blocks.repeaters.push(((*x, y, top), Orientation::South));
+            } else {
+                blocks.redstone.push((*x, y, top));
+            }
+        }
+    }
+
+    for bit in 0..data_width {
+        let y = tile_row(bit);
+
+        // The Data-Input reaches up to the Latch of the last Word and the Data-Output starts at
+        // the Tile of the first Word, both are refreshed at the Start of every Tile
+        let data_in = (0..=end - TILE_WIDTH + 4).map(|x| (x, y));
+        let data_out = (TILE_WIDTH - 1..=end).map(|x| (x, y + 7));
+        for (x, y) in data_in.chain(data_out) {
+            if x % TILE_WIDTH == 1 {
+                blocks.repeaters.push(((x, y, z_pos), Orientation::East));
+            } else {
+                blocks.redstone.push((x, y, z_pos));
+            }
+        }
+
+        for word in 0..words {
+            let x = TILE_WIDTH * word;
+
+            // The Latch, locked by the Write-Column stepping down to its Side
+            blocks.redstone.extend([
+                (x + 4, y + 1, z_pos),
+                (x + 4, y + 2, z_pos),
+                (x + 5, y + 2, z_pos),
+                (x + 4, y + 4, top + 1),
+                (x + 5, y + 4, top + 2),
+                (x + 6, y + 4, z_pos),
+            ]);
+            blocks
+                .repeaters
+                .push(((x + 6, y + 2, z_pos), Orientation::East));
+            blocks
+                .repeaters
+                .push(((x + 6, y + 3, z_pos), Orientation::North));
+
+            // The inverted Value and the Read-Column, stepping down and back up to the Block
+            blocks.redstone.extend([
+                (x + 7, y + 2, z_pos),
+                (x + 10, y + 2, z_pos),
+                (x + 9, y + 5, top + 1),
+                (x + 10, y + 5, top + 2),
+                (x + 11, y + 5, z_pos),
+                (x + 11, y + 4, z_pos),
+                (x + 11, y + 3, z_pos),
+            ]);
+            blocks
+                .solid
+                .extend([(x + 8, y + 2, z_pos), (x + 11, y + 2, z_pos)]);
+            blocks.wall_torches.extend([
+                ((x + 9, y + 2, z_pos), Orientation::West),
+                ((x + 12, y + 2, z_pos), Orientation::West),
+            ]);
+
+            // The Torch joins the Data-Output, the Repeater keeps the other Words out of it
+            blocks.redstone.extend([
+                (x + 13, y + 2, z_pos),
+                (x + 13, y + 3, z_pos),
+                (x + 13, y + 5, z_pos),
+                (x + 13, y + 6, z_pos),
+            ]);
+            blocks
+                .repeaters
+                .push(((x + 13, y + 4, z_pos), Orientation::South));
+        }
+    }
+
+    blocks.place(space, (x_offset, y_offset));
+
+    let size = (end + 1, tile_row(data_width), 1);
+    reserve_around(space, (x_offset, y_offset, z_pos), size, RESERVE_SPACE);
+
+    let in_ports = (0..address_width)
+        .map(|bit| 4 * bit)
+        .chain((0..data_width).map(tile_row))
+        .chain([2 * write_enable_line, 2 * clock_line])
+        .map(|y| (x_offset, y_offset + y, z_pos))
+        .collect();
+    let out_ports = (0..data_width)
+        .map(|bit| (x_offset + end, y_offset + tile_row(bit) + 7, z_pos))
+        .collect();
+
+    (
+        size,
+        PlacedNodeData::Entity {
+            in_ports,
+            out_ports,
+        },
+    )
+}
@@ -53,7 +53,7 @@ where
                 let current_layer_count = self.content.len();
                 let to_add = z - current_layer_count + 1;
                 self.content
-                    .extend(std::iter::repeat(Vec::new()).take(to_add + 1));
+                    .extend(std::iter::repeat_n(Vec::new(), to_add + 1));
 
                 self.content.get_mut(z).unwrap()
             }
@@ -64,7 +64,7 @@ where
             None => {
                 let current_row_count = layer.len();
                 let to_add = y - current_row_count + 1;
-                layer.extend(std::iter::repeat(Vec::new()).take(to_add));
+                layer.extend(std::iter::repeat_n(Vec::new(), to_add));
 
                 layer.get_mut(y).unwrap()
             }
@@ -75,7 +75,7 @@ where
             None => {
                 let current_cell_count = row.len();
                 let to_add = x - current_cell_count + 1;
-                row.extend(std::iter::repeat(T::default()).take(to_add));
+                row.extend(std::iter::repeat_n(T::default(), to_add));
 
                 row.get_mut(x).unwrap()
             }
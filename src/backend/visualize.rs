@@ -98,7 +98,17 @@ fn cells(space: &Space<SpaceCell>) -> svg::node::element::Group {
                         .set("height", SCALE),
                 );
             }
-            SpaceBlock::Repeater { direction } => {
+            SpaceBlock::RedstoneBlock => {
+                result = result.add(
+                    svg::node::element::Rectangle::new()
+                        .set("fill", "#AA0000")
+                        .set("x", x)
+                        .set("y", y)
+                        .set("width", SCALE)
+                        .set("height", SCALE),
+                );
+            }
+            SpaceBlock::Repeater { direction, .. } => {
                 let mut repeater = svg::node::element::Group::new();
                 repeater = repeater.add(
                     svg::node::element::Rectangle::new()
@@ -109,33 +119,36 @@ fn cells(space: &Space<SpaceCell>) -> svg::node::element::Group {
                         .set("height", SCALE),
                 );
 
-                match direction {
+                // The two Torches sit towards the Output-Side of the Repeater
+                let torch_size = SCALE / 5;
+                let torch_positions = match direction {
                     Orientation::East => {
-                        let torch_size = SCALE / 5;
                         let torch_y = y + SCALE / 2 - torch_size / 2;
-                        repeater = repeater
-                            .add(
-                                svg::node::element::Rectangle::new()
-                                    .set("fill", "#FF0000")
-                                    .set("x", x + SCALE / 5 * 4)
-                                    .set("y", torch_y)
-                                    .set("width", torch_size)
-                                    .set("height", torch_size),
-                            )
-                            .add(
-                                svg::node::element::Rectangle::new()
-                                    .set("fill", "#FF0000")
-                                    .set("x", x + SCALE / 5 * 2)
-                                    .set("y", torch_y)
-                                    .set("width", torch_size)
-                                    .set("height", torch_size),
-                            );
+                        [(x + SCALE / 5 * 4, torch_y), (x + SCALE / 5 * 2, torch_y)]
                     }
-                    other => {
-                        dbg!(other);
-                        todo!()
+                    Orientation::West => {
+                        let torch_y = y + SCALE / 2 - torch_size / 2;
+                        [(x, torch_y), (x + SCALE / 5 * 2, torch_y)]
+                    }
+                    Orientation::North => {
+                        let torch_x = x + SCALE / 2 - torch_size / 2;
+                        [(torch_x, y), (torch_x, y + SCALE / 5 * 2)]
+                    }
+                    Orientation::South => {
+                        let torch_x = x + SCALE / 2 - torch_size / 2;
+                        [(torch_x, y + SCALE / 5 * 4), (torch_x, y + SCALE / 5 * 2)]
                     }
                 };
+                for (torch_x, torch_y) in torch_positions {
+                    repeater = repeater.add(
+                        svg::node::element::Rectangle::new()
+                            .set("fill", "#FF0000")
+                            .set("x", torch_x)
+                            .set("y", torch_y)
+                            .set("width", torch_size)
+                            .set("height", torch_size),
+                    );
+                }
 
                 result = result.add(repeater);
             }
@@ -198,10 +211,9 @@ fn cells(space: &Space<SpaceCell>) -> svg::node::element::Group {
                 let torch_size = SCALE / 5;
                 let (x, y) = match direction {
                     Orientation::West => (x, y + SCALE / 2 - torch_size / 2),
-                    other => {
-                        dbg!(other);
-                        todo!()
-                    }
+                    Orientation::East => (x + SCALE - torch_size, y + SCALE / 2 - torch_size / 2),
+                    Orientation::South => (x + SCALE / 2 - torch_size / 2, y + SCALE - torch_size),
+                    Orientation::North => (x + SCALE / 2 - torch_size / 2, y),
                 };
 
                 result = result.add(
@@ -213,6 +225,18 @@ fn cells(space: &Space<SpaceCell>) -> svg::node::element::Group {
                         .set("height", torch_size),
                 );
             }
+            SpaceBlock::Torch => {
+                let torch_size = SCALE / 5;
+
+                result = result.add(
+                    svg::node::element::Rectangle::new()
+                        .set("fill", "#FF0000")
+                        .set("x", x + SCALE / 2 - torch_size / 2)
+                        .set("y", y + SCALE / 2 - torch_size / 2)
+                        .set("width", torch_size)
+                        .set("height", torch_size),
+                );
+            }
         };
     }
 
@@ -1,17 +1,49 @@
-use std::{collections::HashMap, ops::Range, sync::Arc};
+use std::{collections::HashMap, fmt::Display, ops::Range, sync::Arc};
 
 use crate::graph;
 
 #[derive(Debug)]
+struct Source {
+    name: String,
+    content: String,
+    /// The Byte-Offsets at which each Line starts
+    line_starts: Vec<usize>,
+}
+
+impl Source {
+    fn new(name: String, content: String) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        Self {
+            name,
+            content,
+            line_starts,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Span {
     area: Range<usize>,
-    content: Arc<String>,
+    source: Arc<Source>,
 }
 
 impl Span {
     pub fn new<S>(content: S) -> Self
     where
         S: Into<String>,
+    {
+        Self::with_name("<input>", content)
+    }
+
+    /// Creates a new Span covering the entire given Content, which is identified by the given
+    /// Name (usually the Path of the File) in Diagnostics
+    pub fn with_name<N, S>(name: N, content: S) -> Self
+    where
+        N: Into<String>,
+        S: Into<String>,
     {
         let c_str = content.into();
 
@@ -19,21 +51,70 @@ impl Span {
 
         Self {
             area,
-            content: Arc::new(c_str),
+            source: Arc::new(Source::new(name.into(), c_str)),
         }
     }
 
     pub fn content(&self) -> &str {
-        &self.content[self.area.clone()]
+        &self.source.content[self.area.clone()]
     }
 
     #[must_use]
     pub fn sub_span(&self, area: Range<usize>) -> Self {
         Self {
             area,
-            content: self.content.clone(),
+            source: self.source.clone(),
         }
     }
+
+    /// Creates a new Span that starts at the Start of this Span and ends at the End of the other
+    /// Span
+    #[must_use]
+    pub fn join(&self, other: &Self) -> Self {
+        self.sub_span(self.area.start..other.area.end)
+    }
+
+    /// The Name of the Source this Span belongs to
+    pub fn name(&self) -> &str {
+        &self.source.name
+    }
+
+    pub fn start(&self) -> usize {
+        self.area.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.area.end
+    }
+
+    /// Returns the 1-based Line and Column of the given Byte-Offset in the Source, where the
+    /// Column is counted in Characters
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let line_starts = &self.source.line_starts;
+
+        let line = line_starts.partition_point(|start| *start <= offset);
+        let line_start = line_starts[line - 1];
+        let column = self.source.content[line_start..offset].chars().count() + 1;
+
+        (line, column)
+    }
+
+    /// Returns the Content of the given 1-based Line in the Source, without the Line-Ending
+    pub fn line(&self, line: usize) -> &str {
+        let line_starts = &self.source.line_starts;
+
+        let start = match line_starts.get(line - 1) {
+            Some(s) => *s,
+            None => return "",
+        };
+        let end = line_starts
+            .get(line)
+            .map(|e| e - 1)
+            .unwrap_or(self.source.content.len());
+
+        let content = &self.source.content[start..end];
+        content.strip_suffix('\r').unwrap_or(content)
+    }
 }
 
 impl<S> From<S> for Span
@@ -45,45 +126,186 @@ where
     }
 }
 
+mod diagnostic;
+pub use diagnostic::Diagnostic;
+
+/// The Architecture used for Adders, including the ones used by Subtractions and Comparisons
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum AdderKind {
+    /// Every Bit waits for the Carry of the previous Bit, which needs the fewest Gates
+    #[default]
+    Ripple,
+    /// The Carries are computed by a Prefix-Tree, which is faster for wide Buses but needs more
+    /// Gates
+    Lookahead,
+}
+
+/// The Settings that change how the Source is turned into a Graph
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub adder: AdderKind,
+}
+
 mod semantics;
+mod sources;
 mod syntax;
 mod tokens;
 
 #[derive(Debug)]
 pub enum ParseError {
-    Syntax(syntax::Error),
+    Tokens(tokens::Error),
+    Syntax(Vec<syntax::Error>),
+    Semantic(Vec<semantics::Error>),
+    /// A File named in a `use` that could not be read
+    UnreadableFile {
+        file: Span,
+        reason: String,
+    },
+    UnknownTarget(String),
+    NoEntities,
+    /// Loops without any Storage, that only appear once the Entities are inlined into the
+    /// Target, each described by the Names of the Signals in it
+    CombinationalLoops(Vec<Vec<String>>),
 }
 
-impl From<syntax::Error> for ParseError {
-    fn from(e: syntax::Error) -> Self {
-        Self::Syntax(e)
+impl From<semantics::Error> for ParseError {
+    fn from(e: semantics::Error) -> Self {
+        Self::Semantic(vec![e])
     }
 }
 
-pub fn parse<S>(content: S, target: Option<String>) -> Result<graph::normalized::Graph, ParseError>
+impl ParseError {
+    /// Returns the Diagnostics describing this Error, if it can be attributed to a Location in
+    /// the Source
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        match self {
+            Self::Tokens(e) => vec![e.diagnostic()],
+            Self::Syntax(errors) => errors.iter().map(|e| e.diagnostic()).collect(),
+            Self::Semantic(errors) => errors.iter().flat_map(|e| e.diagnostics()).collect(),
+            Self::UnreadableFile { file, reason } => vec![Diagnostic::new(
+                file.clone(),
+                format!("could not read {}: {}", file.content(), reason),
+            )],
+            Self::UnknownTarget(_) | Self::NoEntities | Self::CombinationalLoops(_) => Vec::new(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownTarget(name) => writeln!(f, "error: there is no entity named `{}`", name),
+            Self::NoEntities => writeln!(f, "error: the source does not contain any entity"),
+            Self::CombinationalLoops(loops) => {
+                for signals in loops {
+                    writeln!(
+                        f,
+                        "error: the signals {} form a loop across entities without any storage in between",
+                        semantics::quoted_list(signals)
+                    )?;
+                }
+                Ok(())
+            }
+            _ => {
+                for (index, diagnostic) in self.diagnostics().into_iter().enumerate() {
+                    if index > 0 {
+                        writeln!(f)?;
+                    }
+                    writeln!(f, "{}", diagnostic)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The Documentation of an Entity and its Ports, taken from the `///` Comments in front of them
+#[derive(Debug)]
+pub struct EntityDoc {
+    pub name: String,
+    pub doc: Option<String>,
+    pub in_ports: Vec<(String, Option<String>)>,
+    pub out_ports: Vec<(String, Option<String>)>,
+}
+
+/// Extracts the Documentation of all the Entities in the given Content
+pub fn documentation<S>(content: S) -> Result<Vec<EntityDoc>, ParseError>
 where
     S: Into<Span>,
 {
-    let content_span = content.into();
+    let tokens = tokens::tokenize(content.into()).map_err(ParseError::Tokens)?;
 
-    let tokens = tokens::tokenize(content_span);
+    let (file, syntax_errors) = syntax::parse(tokens);
+    if !syntax_errors.is_empty() {
+        return Err(ParseError::Syntax(syntax_errors));
+    }
+
+    let port_docs = |ports: Vec<syntax::Port>| {
+        ports
+            .into_iter()
+            .map(|p| (p.name.1.content().to_string(), p.doc))
+            .collect()
+    };
 
-    let syntax = syntax::parse(tokens)?;
+    Ok(file
+        .entities
+        .into_iter()
+        .map(|e| EntityDoc {
+            name: e.name.content().to_string(),
+            doc: e.doc,
+            in_ports: port_docs(e.in_ports),
+            out_ports: port_docs(e.out_ports),
+        })
+        .collect())
+}
 
-    let s_entities = semantics::parse(syntax);
+pub fn parse<S>(content: S, target: Option<String>) -> Result<graph::normalized::Graph, ParseError>
+where
+    S: Into<Span>,
+{
+    parse_with_options(content, target, &Options::default())
+}
 
-    let target_entity = match target {
-        Some(t_name) => s_entities.iter().find(|e| e.name == t_name).unwrap(),
-        None => s_entities.get(0).unwrap(),
-    };
+pub fn parse_with_options<S>(
+    content: S,
+    target: Option<String>,
+    options: &Options,
+) -> Result<graph::normalized::Graph, ParseError>
+where
+    S: Into<Span>,
+{
+    let syntax = sources::load(content.into())?;
+    let s_entities = semantics::parse(syntax)?;
 
-    let target_e_graph = target_entity.graph();
     let all_e_graphs: HashMap<_, _> = s_entities
-        .into_iter()
-        .map(|e| (e.name.clone(), e.graph()))
+        .iter()
+        .map(|e| (e.name.clone(), e.graph(options)))
         .collect();
 
+    let errors = semantics::check_loops(&s_entities, &all_e_graphs);
+    if !errors.is_empty() {
+        return Err(ParseError::Semantic(errors));
+    }
+
+    let target_name = match target {
+        Some(t_name) if all_e_graphs.contains_key(&t_name) => t_name,
+        Some(t_name) => return Err(ParseError::UnknownTarget(t_name)),
+        None => s_entities
+            .first()
+            .ok_or(ParseError::NoEntities)?
+            .name
+            .clone(),
+    };
+
+    let target_e_graph = all_e_graphs[&target_name].clone();
     let target_b_graph = target_e_graph.into_builtin(&all_e_graphs);
+    let graph = target_b_graph.into_normalized();
+
+    // Loops within a single Entity are already reported above, with the Statements forming them
+    let loops = graph.combinational_loops();
+    if !loops.is_empty() {
+        return Err(ParseError::CombinationalLoops(loops));
+    }
 
-    Ok(target_b_graph.into_normalized())
+    Ok(graph)
 }
@@ -0,0 +1,77 @@
+use std::fmt::Display;
+
+use super::Span;
+
+/// A Message about a specific Location in the Source, like a Syntax- or Type-Error
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    span: Span,
+    message: String,
+}
+
+impl Diagnostic {
+    pub fn new<S>(span: Span, message: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for Diagnostic {
+    /// Renders the Diagnostic in the following Form
+    /// ```text
+    /// error: expected `;`, found `}`
+    ///  --> adder.mcl:3:20
+    ///   |
+    /// 3 |     (res) = and(a, b)
+    ///   |                      ^
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (line, column) = self.span.position(self.span.start());
+        let line_content = self.span.line(line);
+
+        // Only underline the Part of the Span that is on the first Line
+        let underline_len = self
+            .span
+            .content()
+            .lines()
+            .next()
+            .map(|l| l.chars().count())
+            .unwrap_or(0)
+            .max(1);
+
+        let line_str = line.to_string();
+        let padding = " ".repeat(line_str.len());
+
+        // Keep the Tabs in front of the Span, so the Underline lines up with the Source
+        let underline_offset: String = line_content
+            .chars()
+            .take(column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{}--> {}:{}:{}", padding, self.span.name(), line, column)?;
+        writeln!(f, "{} |", padding)?;
+        writeln!(f, "{} | {}", line_str, line_content.trim_end())?;
+        write!(
+            f,
+            "{} | {}{}",
+            padding,
+            underline_offset,
+            "^".repeat(underline_len)
+        )
+    }
+}
@@ -1,12 +1,35 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    ops::Range,
+};
 
 use crate::graph;
 
-use super::{syntax, tokens::Token};
+mod minimize;
+use minimize::Implicant;
+
+mod connections;
+
+mod generate;
+
+mod rom;
+
+mod arithmetic;
+
+mod table;
+
+use super::{
+    syntax,
+    tokens::{Token, TokenData},
+    AdderKind, Diagnostic, Options, Span,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Type_ {
     Bit,
+    /// A Bus of multiple Bits, which is never only a single Bit wide
+    Bits(u32),
 }
 
 #[derive(Debug, Clone)]
@@ -20,6 +43,8 @@ struct EntityHeader {
     name: String,
     in_ports: Vec<Port>,
     out_ports: Vec<Port>,
+    /// The Values of the Parameters of a generic Entity
+    params: HashMap<String, u32>,
 }
 
 #[derive(Debug, Clone)]
@@ -28,45 +53,118 @@ pub struct Variable {
     ty: Type_,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum BuiltinOp {
     Not,
     And,
     Xor,
     Or,
+    Nand,
+    Nor,
+    Xnor,
+    /// A D-Flip-Flop, storing the Data on the rising Edge of the Clock
+    Dff,
+    /// A D-Latch, passing through the Data while enabled and holding it otherwise
+    Latch,
+    /// A Set-Reset Latch, where Set takes Priority over Reset
+    RsLatch,
+    /// A Monostable, turning on for the given Number of Ticks on the rising Edge of its Input
+    Pulse {
+        length: u32,
+    },
+    /// Selects the first Input if the Select-Bit is off and the second one if it is on
+    Mux,
+    /// Selects one of four Inputs using a two Bit wide Select-Bus
+    Mux4,
+    /// Turns on the single Bit of the Result, whose Index is the Value of the Argument
+    Decode,
+    /// The Sum of two Buses, dropping the final Carry
+    Add,
+    /// The Difference of two Buses, wrapping around below 0
+    Sub,
+    /// Whether two Buses have the same Value
+    Eq,
+    /// Whether the first Bus has a smaller unsigned Value than the second one
+    Less,
+    /// A Read-Only Memory, outputting the Word at the Address given by the Argument, where all
+    /// the Words after the given ones are 0
+    Rom {
+        words: Vec<u64>,
+        data_width: u32,
+    },
+    /// A Random-Access Memory, outputting the Word at the Address and overwriting it with the Data
+    /// while both the Write-Enable and the Clock are on
+    Ram,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Operand {
     Variable(Variable),
     Port(Port),
+    /// Only the Bits in the Range of the inner Operand
+    Slice {
+        inner: Box<Operand>,
+        range: Range<u32>,
+    },
+    /// All the Operands combined into a single Bus, where the first Operand makes up the most
+    /// significant Bits
+    Concat(Vec<Operand>),
+    /// A fixed Value, starting with the least significant Bit
+    Constant(Vec<bool>),
+    /// The single Result of a nested Value, like the Operands of an infix Expression
+    Value {
+        value: Box<BehaviourValue>,
+        ty: Type_,
+    },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum BehaviourValue {
     BuiltinOp {
         op: BuiltinOp,
         arguments: Vec<Operand>,
     },
+    /// Combines all the Bits of the Argument into a single Bit using the Operation
+    Reduce {
+        op: BuiltinOp,
+        argument: Operand,
+    },
     EntityOp {
         op: String,
         arguments: Vec<Operand>,
-        port_count: u32,
+        outputs: Vec<Type_>,
+    },
+    Operands {
+        operands: Vec<Operand>,
+    },
+    /// A Clock without any Inputs, that turns on and off with the given Period in Ticks
+    Clock {
+        period: u32,
     },
-    Variables {
-        vars: Vec<Variable>,
+    /// A Truth-Table as a Sum-of-Products for every Output-Bit, where the Bits of the Implicants
+    /// are the Input-Bits in the Order they were written in, starting with the most significant
+    /// Bit of the first Input
+    Table {
+        inputs: Vec<Operand>,
+        outputs: Vec<Type_>,
+        terms: Vec<Vec<Implicant>>,
     },
 }
 
 #[derive(Debug)]
 pub enum Behaviour {
+    /// Assigns the Value to the Variables, where `None` discards the Result at that Position
     VarAssign {
-        targets: Vec<Variable>,
+        targets: Vec<Option<Variable>>,
         value: BehaviourValue,
+        span: Span,
     },
+    /// Assigns the Value to the Targets, which are either entire Output-Ports or Slices of them,
+    /// or `None` to discard the Result at that Position
     PortAssign {
-        targets: Vec<Port>,
+        targets: Vec<Option<Operand>>,
         value: BehaviourValue,
+        span: Span,
     },
 }
 
@@ -78,456 +176,2695 @@ pub struct Entity {
     behaviour: Vec<Behaviour>,
 }
 
-pub fn parse(raw_entities: Vec<syntax::Entity>) -> Vec<Entity> {
-    let headers: HashMap<String, EntityHeader> = raw_entities
-        .iter()
-        .map(parse_entity_header)
-        .map(|h| (h.name.clone(), h))
-        .collect();
-
-    raw_entities
-        .into_iter()
-        .map(|e| parse_entity(e, &headers))
-        .collect()
+#[derive(Debug)]
+pub enum Error {
+    UnknownType {
+        ty: Span,
+    },
+    UnknownOperand {
+        name: Token,
+    },
+    UnknownPort {
+        name: Span,
+    },
+    UnknownEntity {
+        name: Span,
+    },
+    ArgumentCount {
+        op: Span,
+        expected: usize,
+        got: usize,
+    },
+    TargetCount {
+        statement: Span,
+        expected: usize,
+        got: usize,
+    },
+    /// A Call nested in another Expression, which does not produce exactly one Result
+    NotSingleValue {
+        call: Span,
+        count: usize,
+    },
+    TypeMismatch {
+        span: Span,
+        expected: Type_,
+        got: Type_,
+    },
+    WidthMismatch {
+        op: Span,
+        span: Span,
+        expected: u32,
+        got: u32,
+    },
+    InvalidWidth {
+        width: Span,
+    },
+    InvalidIndex {
+        index: Span,
+    },
+    IndexOutOfRange {
+        operand: Span,
+        ty: Type_,
+    },
+    SlicedVariableTarget {
+        target: Span,
+    },
+    InvalidConstant {
+        constant: Span,
+    },
+    ConstantOverflow {
+        constant: Span,
+        width: u32,
+    },
+    InvalidClockPeriod {
+        period: Span,
+    },
+    InvalidPulseLength {
+        length: Span,
+    },
+    DecodeTooWide {
+        operand: Span,
+        width: u32,
+    },
+    TableTooWide {
+        inputs: Span,
+        width: u32,
+    },
+    /// A quoted Text outside of the Places that refer to a File
+    MisplacedText {
+        text: Span,
+    },
+    ExpectedFileName {
+        span: Span,
+    },
+    RomFile {
+        file: Span,
+        reason: String,
+    },
+    RomAddressTooWide {
+        operand: Span,
+        width: u32,
+    },
+    RamAddressTooWide {
+        operand: Span,
+        width: u32,
+    },
+    ParameterCount {
+        call: Span,
+        expected: usize,
+        got: usize,
+    },
+    InvalidParameter {
+        value: Span,
+    },
+    /// A Bound of a Generate-Loop or a Side of a Generate-Condition, which is not known
+    InvalidNumber {
+        number: Span,
+    },
+    /// A named Connection to a Port that the called Entity doesn't have
+    UnknownConnection {
+        port: Span,
+        entity: String,
+        output: bool,
+    },
+    DuplicateConnection {
+        port: Span,
+        previous: Span,
+        output: bool,
+    },
+    /// The Ports of the called Entity that are not connected, once some of them are connected by
+    /// their Name
+    MissingConnection {
+        call: Span,
+        entity: String,
+        ports: Vec<String>,
+        output: bool,
+    },
+    /// A Connection by Position after a Connection by Name
+    PositionalConnection {
+        connection: Span,
+    },
+    /// A named Argument of something other than an Entity
+    NamedArgument {
+        port: Span,
+        op: Span,
+    },
+    /// A named Target of a Value that is not the Call of an Entity
+    NamedTarget {
+        port: Span,
+    },
+    /// An Entity with the same Name as an earlier one, possibly from another File
+    DuplicateEntity {
+        name: Span,
+        previous: Span,
+    },
+    /// A ROM-File with more Words than the Address can select
+    RomTooLarge {
+        file: Span,
+        words: usize,
+        address_width: u32,
+    },
+    InvalidTableCell {
+        cell: Span,
+    },
+    /// A Side of a Row of a Truth-Table with the wrong Number of Values, where the Span covers
+    /// all the Values of that Side
+    TableRowWidth {
+        cells: Span,
+        expected: u32,
+        got: u32,
+    },
+    /// Two Rows of a Truth-Table that match the same Inputs but produce different Outputs
+    ConflictingRows {
+        row: Span,
+        previous: Span,
+    },
+    /// Variables that depend on themselves without any Storage in between, either because they
+    /// are only assigned to each other or through Logic
+    CombinationalLoop {
+        variables: Vec<String>,
+        statements: Vec<Span>,
+        logic: bool,
+    },
+    /// Entities that use each other in a Cycle, where the first Entity is repeated at the End and
+    /// every Entity uses the next one in the corresponding Call
+    RecursiveEntity {
+        entities: Vec<String>,
+        calls: Vec<Span>,
+    },
+    /// A Call of a generic Entity, whose Instance would be nested deeper than the Limit in other
+    /// Instances, like an Entity that keeps using itself with new Parameters
+    InstanceDepth {
+        entity: String,
+        call: Span,
+        limit: usize,
+    },
 }
 
-fn parse_entity_header(raw_entity: &syntax::Entity) -> EntityHeader {
-    let name = raw_entity.name.content().to_string();
+impl Error {
+    pub fn diagnostic(&self) -> Diagnostic {
+        match self {
+            Self::UnknownType { ty } => {
+                Diagnostic::new(ty.clone(), format!("unknown type `{}`", ty.content()))
+            }
+            Self::UnknownOperand { name } => Diagnostic::new(
+                name.1.clone(),
+                format!(
+                    "`{}` is neither an input port nor a previously assigned variable",
+                    name.1.content()
+                ),
+            ),
+            Self::UnknownPort { name } => Diagnostic::new(
+                name.clone(),
+                format!("`{}` is not an output port of this entity", name.content()),
+            ),
+            Self::UnknownEntity { name } => Diagnostic::new(
+                name.clone(),
+                format!(
+                    "`{}` is neither a builtin operation nor a known entity",
+                    name.content()
+                ),
+            ),
+            Self::ArgumentCount { op, expected, got } => Diagnostic::new(
+                op.clone(),
+                format!(
+                    "`{}` expects {} arguments, but {} were given",
+                    op.content(),
+                    expected,
+                    got
+                ),
+            ),
+            Self::TargetCount {
+                statement,
+                expected,
+                got,
+            } => Diagnostic::new(
+                statement.clone(),
+                format!(
+                    "the value produces {} results, but {} targets were given",
+                    expected, got
+                ),
+            ),
+            Self::NotSingleValue { call, count } => Diagnostic::new(
+                call.clone(),
+                format!(
+                    "the call produces {} results, but only a single result can be used here",
+                    count
+                ),
+            ),
+            Self::TypeMismatch {
+                span,
+                expected,
+                got,
+            } => Diagnostic::new(
+                span.clone(),
+                format!("expected type `{}`, found `{}`", expected, got),
+            ),
+            Self::WidthMismatch {
+                op,
+                span,
+                expected,
+                got,
+            } => Diagnostic::new(
+                span.clone(),
+                format!(
+                    "all the arguments of `{}` need to have the same width, expected width {}, found width {}",
+                    op.content(),
+                    expected,
+                    got
+                ),
+            ),
+            Self::InvalidWidth { width } => Diagnostic::new(
+                width.clone(),
+                format!(
+                    "`{}` is not a valid bus width, expected a number greater than 0",
+                    width.content()
+                ),
+            ),
+            Self::InvalidIndex { index } => Diagnostic::new(
+                index.clone(),
+                format!("`{}` is not a valid bit index", index.content()),
+            ),
+            Self::IndexOutOfRange { operand, ty } => Diagnostic::new(
+                operand.clone(),
+                format!("the selected bits are out of range for type `{}`", ty),
+            ),
+            Self::SlicedVariableTarget { target } => {
+                Diagnostic::new(target.clone(), "variables can only be assigned as a whole")
+            }
+            Self::InvalidConstant { constant } => Diagnostic::new(
+                constant.clone(),
+                format!("`{}` is not a valid constant", constant.content()),
+            ),
+            Self::ConstantOverflow { constant, width } => Diagnostic::new(
+                constant.clone(),
+                format!(
+                    "the constant `{}` does not fit into {} bits",
+                    constant.content(),
+                    width
+                ),
+            ),
+            Self::InvalidClockPeriod { period } => Diagnostic::new(
+                period.clone(),
+                format!(
+                    "the period of a clock needs to be an even number of at least {} ticks, found `{}`",
+                    MIN_CLOCK_PERIOD,
+                    period.content()
+                ),
+            ),
+            Self::InvalidPulseLength { length } => Diagnostic::new(
+                length.clone(),
+                format!(
+                    "the length of a pulse needs to be a number of at least 1 tick, found `{}`",
+                    length.content()
+                ),
+            ),
+            Self::DecodeTooWide { operand, width } => Diagnostic::new(
+                operand.clone(),
+                format!(
+                    "`decode` supports at most {} bits, found width {}",
+                    MAX_DECODE_WIDTH, width
+                ),
+            ),
+            Self::TableTooWide { inputs, width } => Diagnostic::new(
+                inputs.clone(),
+                format!(
+                    "tables support at most {} input bits, found {}",
+                    table::MAX_INPUTS, width
+                ),
+            ),
+            Self::MisplacedText { text } => Diagnostic::new(
+                text.clone(),
+                "a file name is only allowed as the first argument of `rom`",
+            ),
+            Self::ExpectedFileName { span } => Diagnostic::new(
+                span.clone(),
+                format!(
+                    "expected the name of a file in quotes, found `{}`",
+                    span.content()
+                ),
+            ),
+            Self::RomFile { file, reason } => Diagnostic::new(
+                file.clone(),
+                format!("could not load {}: {}", file.content(), reason),
+            ),
+            Self::RomAddressTooWide { operand, width } => Diagnostic::new(
+                operand.clone(),
+                format!(
+                    "`rom` supports at most {} address bits, found width {}",
+                    MAX_ROM_ADDRESS_WIDTH, width
+                ),
+            ),
+            Self::RamAddressTooWide { operand, width } => Diagnostic::new(
+                operand.clone(),
+                format!(
+                    "`ram` supports at most {} address bits, found width {}",
+                    MAX_RAM_ADDRESS_WIDTH, width
+                ),
+            ),
+            Self::ParameterCount {
+                call,
+                expected,
+                got,
+            } => Diagnostic::new(
+                call.clone(),
+                format!(
+                    "`{}` expects {} parameters, but {} were given",
+                    call.content(),
+                    expected,
+                    got
+                ),
+            ),
+            Self::InvalidParameter { value } => Diagnostic::new(
+                value.clone(),
+                format!(
+                    "`{}` is not a valid parameter, expected a number or the name of a parameter",
+                    value.content()
+                ),
+            ),
+            Self::InvalidNumber { number } => Diagnostic::new(
+                number.clone(),
+                format!(
+                    "`{}` is not a valid number, expected a number, a parameter or a loop variable",
+                    number.content()
+                ),
+            ),
+            Self::UnknownConnection {
+                port,
+                entity,
+                output,
+            } => Diagnostic::new(
+                port.clone(),
+                format!(
+                    "`{}` is not an {} port of `{}`",
+                    port.content(),
+                    direction(*output),
+                    entity
+                ),
+            ),
+            Self::DuplicateConnection { port, output, .. } => Diagnostic::new(
+                port.clone(),
+                format!(
+                    "the {} port `{}` is connected multiple times",
+                    direction(*output),
+                    port.content()
+                ),
+            ),
+            Self::MissingConnection {
+                call,
+                entity,
+                ports,
+                output,
+            } => {
+                let message = match ports.as_slice() {
+                    [single] => format!(
+                        "the {} port `{}` of `{}` is not connected",
+                        direction(*output),
+                        single,
+                        entity
+                    ),
+                    _ => format!(
+                        "the {} ports {} of `{}` are not connected",
+                        direction(*output),
+                        quoted_list(ports),
+                        entity
+                    ),
+                };
+
+                Diagnostic::new(call.clone(), message)
+            }
+            Self::PositionalConnection { connection } => Diagnostic::new(
+                connection.clone(),
+                "a connection by position can't follow a connection by name",
+            ),
+            Self::NamedArgument { port, op } => Diagnostic::new(
+                port.clone(),
+                format!(
+                    "`{}` is not an entity, so its arguments can't be named",
+                    op.content()
+                ),
+            ),
+            Self::NamedTarget { port } => Diagnostic::new(
+                port.clone(),
+                "only the results of an entity can be bound by name",
+            ),
+            Self::DuplicateEntity { name, .. } => Diagnostic::new(
+                name.clone(),
+                format!("the entity `{}` is defined multiple times", name.content()),
+            ),
+            Self::RomTooLarge {
+                file,
+                words,
+                address_width,
+            } => Diagnostic::new(
+                file.clone(),
+                format!(
+                    "the file contains {} words, but {} address bits can only select {}",
+                    words,
+                    address_width,
+                    1u64 << address_width
+                ),
+            ),
+            Self::InvalidTableCell { cell } => Diagnostic::new(
+                cell.clone(),
+                format!(
+                    "expected `0`, `1` or `-` in the table, found `{}`",
+                    cell.content()
+                ),
+            ),
+            Self::TableRowWidth {
+                cells,
+                expected,
+                got,
+            } => Diagnostic::new(
+                cells.clone(),
+                format!("expected {} values in the row, found {}", expected, got),
+            ),
+            Self::ConflictingRows { row, .. } => Diagnostic::new(
+                row.clone(),
+                "the row produces different outputs than an earlier row for the same inputs",
+            ),
+            Self::CombinationalLoop {
+                variables,
+                statements,
+                logic,
+            } => Diagnostic::new(
+                statements[0].clone(),
+                if *logic {
+                    format!(
+                        "the variables {} form a loop through logic without any storage in between",
+                        quoted_list(variables)
+                    )
+                } else {
+                    format!(
+                        "the variables {} form a loop without any logic in between",
+                        quoted_list(variables)
+                    )
+                },
+            ),
+            Self::RecursiveEntity { entities, calls } => Diagnostic::new(
+                calls[0].clone(),
+                format!(
+                    "the entity `{}` uses itself through {}",
+                    entities[0],
+                    entities
+                        .iter()
+                        .map(|e| format!("`{}`", e))
+                        .collect::<Vec<_>>()
+                        .join(" -> ")
+                ),
+            ),
+            Self::InstanceDepth {
+                entity,
+                call,
+                limit,
+            } => Diagnostic::new(
+                call.clone(),
+                format!(
+                    "the instance of `{}` would be nested more than {} instances deep, as generic entities keep being instantiated with new parameters",
+                    entity, limit
+                ),
+            ),
+        }
+    }
+
+    /// Returns the Diagnostic for the Error, followed by one for every other Location involved in
+    /// it, like all the Statements of a Loop
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut result = vec![self.diagnostic()];
 
-    let in_ports = parse_ports(&raw_entity.in_ports);
-    let out_ports = parse_ports(&raw_entity.out_ports);
+        match self {
+            Self::CombinationalLoop {
+                variables,
+                statements,
+                ..
+            } => {
+                result.extend(statements.iter().skip(1).map(|s| {
+                    Diagnostic::new(
+                        s.clone(),
+                        format!("the loop of {} continues here", quoted_list(variables)),
+                    )
+                }));
+            }
+            Self::ConflictingRows { previous, .. } => {
+                result.push(Diagnostic::new(previous.clone(), "the earlier row is here"));
+            }
+            Self::DuplicateConnection { previous, .. } => {
+                result.push(Diagnostic::new(
+                    previous.clone(),
+                    "the first connection is here",
+                ));
+            }
+            Self::DuplicateEntity { previous, .. } => {
+                result.push(Diagnostic::new(
+                    previous.clone(),
+                    "the first definition is here",
+                ));
+            }
+            Self::RecursiveEntity { entities, calls } => {
+                result.extend(
+                    calls
+                        .iter()
+                        .zip(entities.windows(2))
+                        .skip(1)
+                        .map(|(call, pair)| {
+                            Diagnostic::new(
+                                call.clone(),
+                                format!("`{}` uses `{}` here", pair[0], pair[1]),
+                            )
+                        }),
+                );
+            }
+            _ => {}
+        };
 
-    EntityHeader {
-        name,
-        in_ports,
-        out_ports,
+        result
     }
 }
 
-fn parse_ports(raw: &[(Token, Token)]) -> Vec<Port> {
-    raw.iter()
-        .map(|(n, ty)| {
-            let name = n.1.content().to_string();
-            let ty = match ty.1.content() {
-                "bit" => Type_::Bit,
-                other => panic!("Unknown Type: {:?}", other),
-            };
+/// The Kind of a Port in Diagnostics
+fn direction(output: bool) -> &'static str {
+    if output {
+        "output"
+    } else {
+        "input"
+    }
+}
 
-            Port { name, ty }
-        })
-        .collect()
+/// Formats the Names like `` `a`, `b` and `c` ``
+pub(super) fn quoted_list(names: &[String]) -> String {
+    let quoted: Vec<_> = names.iter().map(|n| format!("`{}`", n)).collect();
+    match quoted.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+        None => String::new(),
+    }
 }
 
-fn parse_value(
-    value: syntax::BehaviourValue,
-    current_header: &EntityHeader,
-    vars: &HashMap<String, Variable>,
-    headers: &HashMap<String, EntityHeader>,
-) -> (BehaviourValue, Vec<Type_>) {
-    match value {
-        syntax::BehaviourValue::Operation { name, arguments } => {
-            let op_name = name.1.content();
+impl Type_ {
+    /// Returns the Type for a Bus of the given Width, which is a single `Bit` for a Width of 1
+    pub fn with_width(width: u32) -> Self {
+        match width {
+            1 => Self::Bit,
+            other => Self::Bits(other),
+        }
+    }
 
-            let arguments: Vec<Operand> = arguments
-                .into_iter()
-                .map(|a| {
-                    let name = a.1.content();
+    /// The Number of Bits in this Type
+    pub fn width(&self) -> u32 {
+        match self {
+            Self::Bit => 1,
+            Self::Bits(width) => *width,
+        }
+    }
+}
 
-                    if let Some(port) = current_header.in_ports.iter().find(|p| p.name == name) {
-                        return Operand::Port(port.clone());
-                    }
-                    if let Some(var) = vars.get(name) {
-                        return Operand::Variable(var.clone());
-                    }
+impl Display for Type_ {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bit => write!(f, "bit"),
+            Self::Bits(width) => write!(f, "bits[{}]", width),
+        }
+    }
+}
 
-                    dbg!(&a);
-                    todo!("")
-                })
-                .collect();
+pub fn parse(raw_entities: Vec<syntax::Entity>) -> Result<Vec<Entity>, Error> {
+    let mut names: HashMap<&str, &Span> = HashMap::new();
+    for raw_entity in raw_entities.iter() {
+        if let Some(previous) = names.get(raw_entity.name.content()) {
+            return Err(Error::DuplicateEntity {
+                name: raw_entity.name.clone(),
+                previous: (*previous).clone(),
+            });
+        }
+        names.insert(raw_entity.name.content(), &raw_entity.name);
+    }
 
-            match op_name {
-                "and" => {
-                    assert!(arguments.len() == 2);
-                    for a in arguments.iter() {
-                        assert!(a.ty() == &Type_::Bit);
-                    }
+    let instances = instantiate(raw_entities)?;
 
-                    (
-                        BehaviourValue::BuiltinOp {
-                            op: BuiltinOp::And,
-                            arguments,
-                        },
-                        vec![Type_::Bit],
-                    )
-                }
-                "not" => {
-                    assert!(arguments.len() == 1);
-                    for a in arguments.iter() {
-                        assert!(a.ty() == &Type_::Bit);
-                    }
+    let mut headers: HashMap<String, EntityHeader> = HashMap::new();
+    for instance in instances.iter() {
+        let header = parse_entity_header(
+            &instance.raw,
+            instance.name.clone(),
+            instance.params.clone(),
+        )?;
+        headers.insert(header.name.clone(), header);
+    }
 
-                    (
-                        BehaviourValue::BuiltinOp {
-                            op: BuiltinOp::Not,
-                            arguments,
-                        },
-                        vec![Type_::Bit],
-                    )
-                }
-                "xor" => {
-                    assert!(arguments.len() == 2);
-                    for a in arguments.iter() {
-                        assert!(a.ty() == &Type_::Bit);
-                    }
+    let entities: Vec<Entity> = instances
+        .into_iter()
+        .map(|i| parse_entity(i.raw, &i.name, &headers))
+        .collect::<Result<_, _>>()?;
 
-                    (
-                        BehaviourValue::BuiltinOp {
-                            op: BuiltinOp::Xor,
-                            arguments,
-                        },
-                        vec![Type_::Bit],
-                    )
-                }
-                "or" => {
-                    assert!(arguments.len() == 2);
-                    for a in arguments.iter() {
-                        assert!(a.ty() == &Type_::Bit);
-                    }
+    Ok(entities)
+}
 
-                    (
-                        BehaviourValue::BuiltinOp {
-                            op: BuiltinOp::Or,
-                            arguments,
-                        },
-                        vec![Type_::Bit],
-                    )
-                }
-                other => {
-                    let other_header = headers.get(other).unwrap();
+/// Finds the Loops without Storage in the given Graphs of the Entities and the Entities using
+/// themselves, which can only be checked once all the Entities are parsed, so all of them are
+/// reported together
+pub fn check_loops(
+    entities: &[Entity],
+    graphs: &HashMap<String, graph::entity::Graph>,
+) -> Vec<Error> {
+    let mut errors = check_recursion(entities);
+    for entity in entities.iter() {
+        errors.extend(
+            entity
+                .combinational_loops(&graphs[&entity.name])
+                .into_iter()
+                .map(|(variables, statements, logic)| Error::CombinationalLoop {
+                    variables,
+                    statements,
+                    logic,
+                }),
+        );
+    }
 
-                    assert!(arguments.len() == other_header.in_ports.len());
-                    for (a, p) in arguments.iter().zip(other_header.in_ports.iter()) {
-                        assert!(a.ty() == &p.ty);
-                    }
+    errors
+}
 
-                    (
-                        BehaviourValue::EntityOp {
-                            op: other.to_string(),
-                            arguments,
-                            port_count: other_header.out_ports.len() as u32,
-                        },
-                        other_header
-                            .out_ports
-                            .iter()
-                            .map(|p| p.ty.clone())
-                            .collect(),
-                    )
-                }
-            }
-        }
-        syntax::BehaviourValue::Variables { vars: raw_vars } => {
-            let var_list: Vec<_> = raw_vars
-                .into_iter()
-                .map(|v| vars.get(v.1.content()).unwrap().clone())
-                .collect();
+/// An Entity together with the Values of its Parameters, which are empty for a regular Entity
+struct Instance {
+    raw: syntax::Entity,
+    name: String,
+    params: HashMap<String, u32>,
+    /// The Number of generic Instances on the Way from a regular Entity to this Instance
+    depth: usize,
+}
+
+/// The Name of the Instance of a generic Entity, like `Adder<4>`
+fn instance_name(name: &str, values: &[u32]) -> String {
+    let values: Vec<_> = values.iter().map(|v| v.to_string()).collect();
+    format!("{}<{}>", name, values.join(", "))
+}
+
+/// Resolves the Value of a Parameter, which is a Sum of Numbers and the Names of the Parameters
+/// of the current Entity
+fn parse_parameter(number: &syntax::Number, params: &HashMap<String, u32>) -> Result<u32, Error> {
+    evaluate(number, params).ok_or_else(|| Error::InvalidParameter {
+        value: number.span.clone(),
+    })
+}
+
+/// The Text of a Literal, which differs from its Source for the Names and Numbers that were
+/// replaced while elaborating the Generate-Blocks
+fn literal(token: &Token) -> &str {
+    match &token.0 {
+        TokenData::Literal(text) => text,
+        _ => token.1.content(),
+    }
+}
+
+/// Parses a plain decimal Number or the Name of a Parameter with a known Value
+fn parse_number(token: &Token, params: &HashMap<String, u32>) -> Option<u32> {
+    let content = literal(token);
+    content
+        .parse::<u32>()
+        .ok()
+        .or_else(|| params.get(content).copied())
+}
 
-            let types: Vec<_> = var_list.iter().map(|v| v.ty.clone()).collect();
+/// Computes the Value of a Sum like `i + 1`, which must not become negative
+fn evaluate(number: &syntax::Number, params: &HashMap<String, u32>) -> Option<u32> {
+    let mut value = parse_number(&number.first, params)?;
+    for (op, term) in number.rest.iter() {
+        let term = parse_number(term, params)?;
+        value = match op.0 {
+            TokenData::Minus => value.checked_sub(term)?,
+            _ => value.checked_add(term)?,
+        };
+    }
 
-            let value = BehaviourValue::Variables { vars: var_list };
+    Some(value)
+}
 
-            (value, types)
+/// Collects the Names and Parameters of all the Calls in the Expression, including nested ones
+fn collect_calls(expression: &syntax::Expression, calls: &mut Vec<(Token, Vec<syntax::Number>)>) {
+    match expression {
+        syntax::Expression::Call {
+            name,
+            parameters,
+            arguments,
+            ..
+        } => {
+            calls.push((name.clone(), parameters.clone()));
+            arguments
+                .iter()
+                .for_each(|a| collect_calls(&a.value, calls));
         }
+        syntax::Expression::Not { inner, .. } => collect_calls(inner, calls),
+        syntax::Expression::Binary { left, right, .. } => {
+            collect_calls(left, calls);
+            collect_calls(right, calls);
+        }
+        syntax::Expression::Operand(_) | syntax::Expression::Text(_) => {}
     }
 }
 
-fn parse_entity(raw_entity: syntax::Entity, headers: &HashMap<String, EntityHeader>) -> Entity {
-    let name = raw_entity.name.content();
-    let current_header = headers.get(name).unwrap().clone();
+/// Collects the Names of all the Operands in the Expression, including the Arguments of Calls
+fn collect_operands(expression: &syntax::Expression, names: &mut Vec<String>) {
+    match expression {
+        syntax::Expression::Operand(operand) => names.push(literal(&operand.name).to_string()),
+        syntax::Expression::Call { arguments, .. } => arguments
+            .iter()
+            .for_each(|a| collect_operands(&a.value, names)),
+        syntax::Expression::Not { inner, .. } => collect_operands(inner, names),
+        syntax::Expression::Binary { left, right, .. } => {
+            collect_operands(left, names);
+            collect_operands(right, names);
+        }
+        syntax::Expression::Text(_) => {}
+    }
+}
 
-    let mut behaviour: Vec<Behaviour> = Vec::new();
-    let mut vars: HashMap<String, Variable> = HashMap::new();
+/// The largest Number of generic Entities that are instantiated from each other in a Chain, which
+/// is only exceeded by an Entity that keeps using itself with new Parameters
+const MAX_INSTANCE_DEPTH: usize = 256;
+
+/// Monomorphises the generic Entities, so every generic Entity is instantiated once for every
+/// Set of Parameters it is used with, starting from the Uses in the regular Entities. The regular
+/// Entities come first, in the Order they were written in
+///
+/// The Behaviour of a generic Entity is only checked for every Instance of it, as the Widths and
+/// Generate-Blocks depend on the Parameters, so a generic Entity that is never used is not checked
+/// beyond its Syntax and the Types of its Parameters
+fn instantiate(raw_entities: Vec<syntax::Entity>) -> Result<Vec<Instance>, Error> {
+    let (generics, regular): (Vec<_>, Vec<_>) =
+        raw_entities.into_iter().partition(|e| !e.params.is_empty());
+
+    for param in generics.iter().flat_map(|g| g.params.iter()) {
+        if param.ty.1.content() != "int" {
+            return Err(Error::UnknownType {
+                ty: param.ty.1.clone(),
+            });
+        }
+    }
+    let generics: HashMap<&str, &syntax::Entity> =
+        generics.iter().map(|g| (g.name.content(), g)).collect();
 
-    for stmnt in raw_entity.behaviour {
-        match stmnt {
-            syntax::BehaviourStatement::VarAssign { targets, value } => {
-                let (b_value, value_types) = parse_value(value, &current_header, &vars, headers);
+    let mut instances: Vec<Instance> = regular
+        .into_iter()
+        .map(|raw| Instance {
+            name: raw.name.content().to_string(),
+            raw,
+            params: HashMap::new(),
+            depth: 0,
+        })
+        .collect();
+    let mut known: HashSet<String> = instances.iter().map(|i| i.name.clone()).collect();
 
-                assert!(value_types.len() == targets.len());
+    let mut next = 0;
+    while next < instances.len() {
+        let mut calls = Vec::new();
+        let instance = &mut instances[next];
+        let raw_behaviour = std::mem::take(&mut instance.raw.behaviour);
+        instance.raw.behaviour = generate::elaborate(raw_behaviour, &instance.params)?;
 
-                let target_vars: Vec<Variable> = targets
-                    .into_iter()
-                    .zip(value_types.into_iter())
-                    .map(|(v, ty)| {
-                        let name = v.1.content().to_string();
-                        let var = Variable {
-                            name: name.clone(),
-                            ty,
-                        };
+        for stmnt in instance.raw.behaviour.iter() {
+            match stmnt {
+                syntax::BehaviourStatement::PortAssign { value, .. }
+                | syntax::BehaviourStatement::VarAssign { value, .. } => value
+                    .values
+                    .iter()
+                    .for_each(|v| collect_calls(v, &mut calls)),
+                syntax::BehaviourStatement::Table { inputs, .. } => {
+                    inputs.iter().for_each(|i| collect_calls(i, &mut calls))
+                }
+                syntax::BehaviourStatement::For { .. } | syntax::BehaviourStatement::If { .. } => {
+                    unreachable!("Generate-Blocks are already elaborated")
+                }
+            }
+        }
 
-                        vars.insert(name, var.clone());
-                        var
+        for (name, parameters) in calls {
+            let generic = match generics.get(name.1.content()) {
+                Some(g) => g,
+                None if parameters.is_empty() => continue,
+                None => {
+                    return Err(Error::ParameterCount {
+                        call: name.1,
+                        expected: 0,
+                        got: parameters.len(),
                     })
-                    .collect();
-
-                behaviour.push(Behaviour::VarAssign {
-                    targets: target_vars,
-                    value: b_value,
+                }
+            };
+            if parameters.len() != generic.params.len() {
+                return Err(Error::ParameterCount {
+                    call: name.1,
+                    expected: generic.params.len(),
+                    got: parameters.len(),
                 });
             }
-            syntax::BehaviourStatement::PortAssign { targets, value } => {
-                let (b_value, value_types) = parse_value(value, &current_header, &vars, headers);
 
-                let target_ports: Vec<_> = targets
-                    .into_iter()
-                    .map(|p_token| {
-                        let p_name = p_token.1.content();
-
-                        current_header
-                            .out_ports
-                            .iter()
-                            .find(|p| p.name == p_name)
-                            .unwrap()
-                            .clone()
-                    })
-                    .collect();
+            let values: Vec<u32> = parameters
+                .iter()
+                .map(|p| parse_parameter(p, &instances[next].params))
+                .collect::<Result<_, _>>()?;
+
+            let instance = instance_name(name.1.content(), &values);
+            if known.insert(instance.clone()) {
+                let depth = instances[next].depth + 1;
+                if depth > MAX_INSTANCE_DEPTH {
+                    return Err(Error::InstanceDepth {
+                        entity: name.1.content().to_string(),
+                        call: name.1,
+                        limit: MAX_INSTANCE_DEPTH,
+                    });
+                }
 
-                target_ports
+                let params = generic
+                    .params
                     .iter()
-                    .zip(value_types.into_iter())
-                    .for_each(|(p, t)| {
-                        assert!(p.ty == t);
-                    });
+                    .map(|p| p.name.1.content().to_string())
+                    .zip(values)
+                    .collect();
 
-                behaviour.push(Behaviour::PortAssign {
-                    targets: target_ports,
-                    value: b_value,
+                instances.push(Instance {
+                    raw: (*generic).clone(),
+                    name: instance,
+                    params,
+                    depth,
                 });
             }
-        };
-    }
+        }
 
-    Entity {
-        name: current_header.name,
-        in_ports: current_header.in_ports,
-        out_ports: current_header.out_ports,
-        behaviour,
+        next += 1;
     }
-}
 
-impl Operand {
-    pub fn ty(&self) -> &Type_ {
-        match self {
-            Self::Port(p) => &p.ty,
-            Self::Variable(v) => &v.ty,
-        }
-    }
+    Ok(instances)
 }
 
-impl BehaviourValue {
-    fn to_graph(
-        &self,
-        node_id: u32,
-        in_ports: &HashMap<String, u32>,
-        var_ids: &HashMap<String, u32>,
-    ) -> (
-        Option<graph::entity::Node>,
-        Vec<graph::entity::Edge>,
-        Vec<(u32, u32)>,
-    ) {
-        match self {
-            Self::BuiltinOp { op, arguments } => {
-                let (node_ty, sources) = match op {
-                    BuiltinOp::And => (
-                        graph::entity::NodeType::BuiltinOp {
-                            op: graph::entity::BuiltinOp::And,
-                        },
-                        vec![(node_id, 0)],
-                    ),
-                    BuiltinOp::Xor => (
-                        graph::entity::NodeType::BuiltinOp {
-                            op: graph::entity::BuiltinOp::Xor,
-                        },
-                        vec![(node_id, 0)],
-                    ),
-                    BuiltinOp::Not => {
-                        todo!()
-                    }
-                    BuiltinOp::Or => (
-                        graph::entity::NodeType::BuiltinOp {
-                            op: graph::entity::BuiltinOp::Or,
-                        },
-                        vec![(node_id, 0)],
-                    ),
-                };
+/// Makes sure that no Entity uses itself, either directly or through other Entities, as it could
+/// never be fully expanded. Every Group of Entities, that use each other, is reported once
+fn check_recursion(entities: &[Entity]) -> Vec<Error> {
+    let uses: HashMap<&str, Vec<(String, Span)>> = entities
+        .iter()
+        .map(|e| (e.name.as_str(), e.used_entities()))
+        .collect();
 
-                let edges = arguments
-                    .iter()
-                    .enumerate()
-                    .map(|(index, arg)| match arg {
-                        Operand::Port(p) => {
-                            let src_id = in_ports.get(&p.name).unwrap();
+    /// Searches for a Cycle starting from the Entity, where the Path contains the Entities used
+    /// to get there and the Calls that were followed
+    fn find_cycle<'e>(
+        name: &'e str,
+        uses: &'e HashMap<&str, Vec<(String, Span)>>,
+        path: &mut Vec<(&'e str, Span)>,
+        done: &mut HashSet<&'e str>,
+    ) -> Option<Vec<(&'e str, Span)>> {
+        for (used, call) in uses.get(name).into_iter().flatten() {
+            path.push((name, call.clone()));
+
+            if let Some(start) = path.iter().position(|(n, _)| n == used) {
+                return Some(path[start..].to_vec());
+            }
+            if !done.contains(used.as_str()) {
+                if let Some(cycle) = find_cycle(used, uses, path, done) {
+                    return Some(cycle);
+                }
+            }
 
-                            graph::entity::Edge::new(*src_id, 0, node_id, index as u32)
-                        }
-                        Operand::Variable(v) => {
-                            let src_id = match var_ids.get(&v.name) {
-                                Some(i) => i,
-                                None => {
-                                    panic!("Unknown Variable: {}", v.name)
-                                }
-                            };
+            path.pop();
+        }
 
-                            graph::entity::Edge::new(*src_id, 0, node_id, index as u32)
-                        }
-                    })
-                    .collect();
+        done.insert(name);
+        None
+    }
 
-                let node = graph::entity::Node::new(node_id, node_ty);
-                (Some(node), edges, sources)
+    /// Returns all the Entities the Entity uses, directly or through other Entities
+    fn reachable<'e>(
+        name: &'e str,
+        uses: &'e HashMap<&str, Vec<(String, Span)>>,
+    ) -> HashSet<&'e str> {
+        let mut result = HashSet::new();
+        let mut pending = vec![name];
+        while let Some(current) = pending.pop() {
+            for (used, _) in uses.get(current).into_iter().flatten() {
+                if result.insert(used.as_str()) {
+                    pending.push(used);
+                }
             }
-            Self::EntityOp {
-                op,
-                arguments,
-                port_count,
-            } => {
-                let node = graph::entity::Node::new(
-                    node_id,
-                    graph::entity::NodeType::EntityOp { name: op.clone() },
-                );
+        }
 
-                let edges: Vec<_> = arguments
-                    .iter()
-                    .enumerate()
-                    .map(|(index, arg)| match arg {
-                        Operand::Port(p) => {
-                            let src_id = in_ports.get(&p.name).unwrap();
+        result
+    }
 
-                            graph::entity::Edge::new(*src_id, 0, node_id, index as u32)
-                        }
-                        Operand::Variable(v) => {
-                            let src_id = var_ids.get(&v.name).unwrap();
+    let mut errors = Vec::new();
+    let mut done = HashSet::new();
+    for entity in entities.iter() {
+        while !done.contains(entity.name.as_str()) {
+            let cycle = match find_cycle(&entity.name, &uses, &mut Vec::new(), &mut done) {
+                Some(c) => c,
+                None => break,
+            };
 
-                            graph::entity::Edge::new(*src_id, 0, node_id, index as u32)
-                        }
-                    })
+            // All the Entities that use each other with the ones of the Cycle are part of the
+            // same Recursion, so none of them is searched again
+            let start = cycle[0].0;
+            done.extend(
+                reachable(start, &uses)
+                    .into_iter()
+                    .filter(|n| reachable(n, &uses).contains(start)),
+            );
+
+            let mut names: Vec<_> = cycle.iter().map(|(n, _)| n.to_string()).collect();
+            names.push(names[0].clone());
+
+            errors.push(Error::RecursiveEntity {
+                entities: names,
+                calls: cycle.into_iter().map(|(_, c)| c).collect(),
+            });
+        }
+    }
+
+    errors
+}
+
+fn parse_entity_header(
+    raw_entity: &syntax::Entity,
+    name: String,
+    params: HashMap<String, u32>,
+) -> Result<EntityHeader, Error> {
+    let in_ports = parse_ports(&raw_entity.in_ports, &params)?;
+    let out_ports = parse_ports(&raw_entity.out_ports, &params)?;
+
+    Ok(EntityHeader {
+        name,
+        in_ports,
+        out_ports,
+        params,
+    })
+}
+
+fn parse_type(raw: &syntax::Type, params: &HashMap<String, u32>) -> Result<Type_, Error> {
+    match (raw.name.1.content(), &raw.width) {
+        ("bit", None) => Ok(Type_::Bit),
+        ("bits", Some(width)) => match evaluate(width, params) {
+            Some(w) if w > 0 => Ok(Type_::with_width(w)),
+            _ => Err(Error::InvalidWidth {
+                width: width.span.clone(),
+            }),
+        },
+        _ => Err(Error::UnknownType {
+            ty: raw.span.clone(),
+        }),
+    }
+}
+
+fn parse_ports(raw: &[syntax::Port], params: &HashMap<String, u32>) -> Result<Vec<Port>, Error> {
+    raw.iter()
+        .map(|p| {
+            let name = p.name.1.content().to_string();
+            let ty = parse_type(&p.ty, params)?;
+
+            Ok(Port { name, ty })
+        })
+        .collect()
+}
+
+/// Parses a Constant like `0`, `1` or a sized Constant like `8'b1010_0001`, `8'hA1` or `8'd161`,
+/// returning its Bits starting with the least significant Bit.
+///
+/// Returns None if the Token is not meant to be a Constant at all
+fn parse_constant(token: &Token) -> Option<Result<Vec<bool>, Error>> {
+    let content = token.1.content();
+    match content {
+        "0" => return Some(Ok(vec![false])),
+        "1" => return Some(Ok(vec![true])),
+        _ => {}
+    };
+
+    let (width, value) = content.split_once('\'')?;
+    if width.is_empty() || !width.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let invalid = || Error::InvalidConstant {
+        constant: token.1.clone(),
+    };
+
+    let width = match width.parse::<u32>() {
+        Ok(w) if w > 0 => w,
+        _ => return Some(Err(invalid())),
+    };
+
+    let mut chars = value.chars();
+    let (radix, bits_per_digit) = match chars.next() {
+        Some('b') => (2, 1),
+        Some('h') => (16, 4),
+        Some('d') => (10, 0),
+        _ => return Some(Err(invalid())),
+    };
+    let digits: String = chars.filter(|c| *c != '_').collect();
+    if digits.is_empty() {
+        return Some(Err(invalid()));
+    }
+
+    // The Bits of the Value, starting with the least significant Bit
+    let mut bits = Vec::new();
+    if bits_per_digit == 0 {
+        let mut number = match digits.parse::<u128>() {
+            Ok(n) => n,
+            Err(_) => return Some(Err(invalid())),
+        };
+        while number > 0 {
+            bits.push(number & 1 == 1);
+            number >>= 1;
+        }
+    } else {
+        for c in digits.chars().rev() {
+            let digit = match c.to_digit(radix) {
+                Some(d) => d,
+                None => return Some(Err(invalid())),
+            };
+            bits.extend((0..bits_per_digit).map(|b| digit & (1 << b) != 0));
+        }
+    }
+
+    if bits.iter().skip(width as usize).any(|b| *b) {
+        return Some(Err(Error::ConstantOverflow {
+            constant: token.1.clone(),
+            width,
+        }));
+    }
+    bits.resize(width as usize, false);
+
+    Some(Ok(bits))
+}
+
+fn parse_bit_index(index: &syntax::Number, params: &HashMap<String, u32>) -> Result<u32, Error> {
+    evaluate(index, params).ok_or_else(|| Error::InvalidIndex {
+        index: index.span.clone(),
+    })
+}
+
+/// The shortest Period of a Clock, where a single Torch and Repeater turn on and off in turns
+const MIN_CLOCK_PERIOD: u32 = 4;
+
+/// Parses a Number of Ticks, which needs to be given as a plain decimal Number or the Name of a
+/// Parameter
+fn parse_ticks(raw: &syntax::Expression, params: &HashMap<String, u32>) -> Option<u32> {
+    match raw {
+        syntax::Expression::Operand(syntax::Operand {
+            name, index: None, ..
+        }) => parse_number(name, params),
+        _ => None,
+    }
+}
+
+/// Resolves the Operand, where the `lookup` is used to find the Port or Variable referenced by
+/// the Name of the Operand
+fn parse_operand<F>(
+    raw: syntax::Operand,
+    params: &HashMap<String, u32>,
+    lookup: F,
+) -> Result<(Operand, Span), Error>
+where
+    F: Fn(&str) -> Option<Operand>,
+{
+    let base = lookup(literal(&raw.name)).ok_or_else(|| Error::UnknownOperand {
+        name: raw.name.clone(),
+    })?;
+
+    let range = match &raw.index {
+        None => return Ok((base, raw.span)),
+        Some(syntax::Index::Bit(index)) => {
+            let index = parse_bit_index(index, params)?;
+            index..index + 1
+        }
+        Some(syntax::Index::Range { high, low }) => {
+            let high_index = parse_bit_index(high, params)?;
+            let low_index = parse_bit_index(low, params)?;
+            if high_index < low_index {
+                return Err(Error::InvalidIndex {
+                    index: high.span.join(&low.span),
+                });
+            }
+
+            low_index..high_index + 1
+        }
+    };
+
+    let ty = base.ty();
+    if range.end > ty.width() {
+        return Err(Error::IndexOutOfRange {
+            operand: raw.span,
+            ty,
+        });
+    }
+
+    Ok((
+        Operand::Slice {
+            inner: Box::new(base),
+            range,
+        },
+        raw.span,
+    ))
+}
+
+/// Checks that the Operation got the expected Number of Arguments and that all of them have the
+/// same Width, returning the Type of the Arguments
+fn check_bitwise_args(
+    op: &Token,
+    arguments: &[(Operand, Span)],
+    expected: usize,
+) -> Result<Type_, Error> {
+    if arguments.len() != expected {
+        return Err(Error::ArgumentCount {
+            op: op.1.clone(),
+            expected,
+            got: arguments.len(),
+        });
+    }
+
+    let ty = arguments[0].0.ty();
+    for (a, span) in arguments.iter().skip(1) {
+        if a.ty().width() != ty.width() {
+            return Err(Error::WidthMismatch {
+                op: op.1.clone(),
+                span: span.clone(),
+                expected: ty.width(),
+                got: a.ty().width(),
+            });
+        }
+    }
+
+    Ok(ty)
+}
+
+/// Checks the Arguments of a clocked Element, where the Data may be a Bus but the Clock is always
+/// a single Bit shared by all the Bits of the Data, returning the Type of the Data
+fn check_clocked_args(op: &Token, arguments: &[(Operand, Span)]) -> Result<Type_, Error> {
+    if arguments.len() != 2 {
+        return Err(Error::ArgumentCount {
+            op: op.1.clone(),
+            expected: 2,
+            got: arguments.len(),
+        });
+    }
+
+    let (clock, span) = &arguments[1];
+    if clock.ty() != Type_::Bit {
+        return Err(Error::TypeMismatch {
+            span: span.clone(),
+            expected: Type_::Bit,
+            got: clock.ty(),
+        });
+    }
+
+    Ok(arguments[0].0.ty())
+}
+
+/// Checks the Arguments of a Multiplexer, which are the Select-Operand of the given Type followed
+/// by the Inputs of the same Width, returning the Type of the Inputs
+fn check_select_args(
+    op: &Token,
+    arguments: &[(Operand, Span)],
+    select: Type_,
+    inputs: usize,
+) -> Result<Type_, Error> {
+    if arguments.len() != inputs + 1 {
+        return Err(Error::ArgumentCount {
+            op: op.1.clone(),
+            expected: inputs + 1,
+            got: arguments.len(),
+        });
+    }
+
+    let (sel, span) = &arguments[0];
+    if sel.ty() != select {
+        return Err(Error::TypeMismatch {
+            span: span.clone(),
+            expected: select,
+            got: sel.ty(),
+        });
+    }
+
+    check_bitwise_args(op, &arguments[1..], inputs)
+}
+
+/// The widest Bus that can be decoded, as the Result already has 256 Bits
+const MAX_DECODE_WIDTH: u32 = 8;
+
+/// The widest Address of a ROM, which keeps the Layout of its Decoder reasonably small
+const MAX_ROM_ADDRESS_WIDTH: u32 = 16;
+
+/// The widest Address of a RAM, as every Word needs its own Column of Tiles in the Layout
+const MAX_RAM_ADDRESS_WIDTH: u32 = 6;
+
+/// Checks the Arguments of a RAM, which are the Address, the Data to write and the single Bits of
+/// the Write-Enable and the Clock, returning the Type of the Data
+fn check_ram_args(op: &Token, arguments: &[(Operand, Span)]) -> Result<Type_, Error> {
+    if arguments.len() != 4 {
+        return Err(Error::ArgumentCount {
+            op: op.1.clone(),
+            expected: 4,
+            got: arguments.len(),
+        });
+    }
+
+    let (address, address_span) = &arguments[0];
+    if address.ty().width() > MAX_RAM_ADDRESS_WIDTH {
+        return Err(Error::RamAddressTooWide {
+            operand: address_span.clone(),
+            width: address.ty().width(),
+        });
+    }
+
+    for (control, span) in &arguments[2..] {
+        if control.ty() != Type_::Bit {
+            return Err(Error::TypeMismatch {
+                span: span.clone(),
+                expected: Type_::Bit,
+                got: control.ty(),
+            });
+        }
+    }
+
+    Ok(arguments[1].0.ty())
+}
+
+/// Finds the Input-Port or Variable with the given Name, that can be used as a Source of a Value
+fn lookup_source(
+    name: &str,
+    current_header: &EntityHeader,
+    vars: &HashMap<String, Variable>,
+) -> Option<Operand> {
+    if let Some(port) = current_header.in_ports.iter().find(|p| p.name == name) {
+        return Some(Operand::Port(port.clone()));
+    }
+
+    vars.get(name).map(|v| Operand::Variable(v.clone()))
+}
+
+/// Resolves the infix Expression into a single Operand
+fn parse_expression(
+    expression: syntax::Expression,
+    current_header: &EntityHeader,
+    vars: &HashMap<String, Variable>,
+    headers: &HashMap<String, EntityHeader>,
+    roms: &rom::Files,
+) -> Result<(Operand, Span), Error> {
+    match expression {
+        syntax::Expression::Operand(operand) => {
+            let constant = operand
+                .index
+                .is_none()
+                .then(|| parse_constant(&operand.name))
+                .flatten();
+            if let Some(bits) = constant {
+                return Ok((Operand::Constant(bits?), operand.span));
+            }
+
+            parse_operand(operand, &current_header.params, |name| {
+                lookup_source(name, current_header, vars)
+            })
+        }
+        syntax::Expression::Call {
+            name,
+            parameters,
+            arguments,
+            span,
+        } => {
+            let (value, mut types) = parse_call(
+                name,
+                parameters,
+                arguments,
+                current_header,
+                vars,
+                headers,
+                roms,
+            )?;
+            if types.len() != 1 {
+                return Err(Error::NotSingleValue {
+                    call: span,
+                    count: types.len(),
+                });
+            }
+
+            Ok((
+                Operand::Value {
+                    value: Box::new(value),
+                    ty: types.remove(0),
+                },
+                span,
+            ))
+        }
+        syntax::Expression::Text(text) => Err(Error::MisplacedText { text: text.1 }),
+        syntax::Expression::Not { inner, span, .. } => {
+            let (inner, _) = parse_expression(*inner, current_header, vars, headers, roms)?;
+            let ty = inner.ty();
+
+            let value = BehaviourValue::BuiltinOp {
+                op: BuiltinOp::Not,
+                arguments: vec![inner],
+            };
+            Ok((
+                Operand::Value {
+                    value: Box::new(value),
+                    ty,
+                },
+                span,
+            ))
+        }
+        syntax::Expression::Binary {
+            op,
+            left,
+            right,
+            span,
+        } => {
+            let builtin = match op.0 {
+                TokenData::Ampersand => BuiltinOp::And,
+                TokenData::Pipe => BuiltinOp::Or,
+                TokenData::Caret => BuiltinOp::Xor,
+                TokenData::Plus => BuiltinOp::Add,
+                TokenData::Minus => BuiltinOp::Sub,
+                TokenData::Equals => BuiltinOp::Eq,
+                TokenData::Less => BuiltinOp::Less,
+                _ => unreachable!("Unknown binary Operator: {:?}", op),
+            };
+
+            let arguments = [
+                parse_expression(*left, current_header, vars, headers, roms)?,
+                parse_expression(*right, current_header, vars, headers, roms)?,
+            ];
+            let ty = check_bitwise_args(&op, &arguments, 2)?;
+            // Comparisons result in a single Bit, no matter how wide the compared Buses are
+            let ty = match builtin {
+                BuiltinOp::Eq | BuiltinOp::Less => Type_::Bit,
+                _ => ty,
+            };
+
+            let value = BehaviourValue::BuiltinOp {
+                op: builtin,
+                arguments: arguments.into_iter().map(|(a, _)| a).collect(),
+            };
+            Ok((
+                Operand::Value {
+                    value: Box::new(value),
+                    ty,
+                },
+                span,
+            ))
+        }
+    }
+}
+
+/// The Name of the called Builtin or Entity, where a generic Entity is called by the Name of its
+/// Instance for the given Parameters
+fn call_name(
+    name: &Token,
+    parameters: &[syntax::Number],
+    current_header: &EntityHeader,
+) -> Result<String, Error> {
+    if parameters.is_empty() {
+        return Ok(name.1.content().to_string());
+    }
+
+    let values: Vec<u32> = parameters
+        .iter()
+        .map(|p| parse_parameter(p, &current_header.params))
+        .collect::<Result<_, _>>()?;
+    Ok(instance_name(name.1.content(), &values))
+}
+
+/// Resolves the Call of a Builtin or another Entity, returning the Value together with the Types
+/// of all of its Results
+fn parse_call(
+    name: Token,
+    parameters: Vec<syntax::Number>,
+    arguments: Vec<syntax::Argument>,
+    current_header: &EntityHeader,
+    vars: &HashMap<String, Variable>,
+    headers: &HashMap<String, EntityHeader>,
+    roms: &rom::Files,
+) -> Result<(BehaviourValue, Vec<Type_>), Error> {
+    let op_name = call_name(&name, &parameters, current_header)?;
+    let op_name = op_name.as_str();
+
+    // The named Arguments of Entities are already ordered by their Ports
+    if let Some(port) = arguments.iter().find_map(|a| a.port.as_ref()) {
+        return Err(Error::NamedArgument {
+            port: port.1.clone(),
+            op: name.1.clone(),
+        });
+    }
+    let arguments: Vec<syntax::Expression> = arguments.into_iter().map(|a| a.value).collect();
+
+    // The Period of a Clock and the Length of a Pulse are fixed Numbers of Ticks, known once the
+    // Parameters of a generic Entity are
+    match (op_name, arguments.as_slice()) {
+        ("clock", [period]) => {
+            let period = match parse_ticks(period, &current_header.params) {
+                Some(p) if p >= MIN_CLOCK_PERIOD && p.is_multiple_of(2) => p,
+                _ => {
+                    return Err(Error::InvalidClockPeriod {
+                        period: period.span().clone(),
+                    })
+                }
+            };
+
+            return Ok((BehaviourValue::Clock { period }, vec![Type_::Bit]));
+        }
+        ("clock", _) => {
+            return Err(Error::ArgumentCount {
+                op: name.1.clone(),
+                expected: 1,
+                got: arguments.len(),
+            })
+        }
+        ("pulse", [_, length]) => {
+            let length = match parse_ticks(length, &current_header.params) {
+                Some(l) if l >= 1 => l,
+                _ => {
+                    return Err(Error::InvalidPulseLength {
+                        length: length.span().clone(),
+                    })
+                }
+            };
+
+            let trigger = arguments.into_iter().next().unwrap();
+            let (trigger, _) = parse_expression(trigger, current_header, vars, headers, roms)?;
+            let ty = trigger.ty();
+
+            return Ok((
+                BehaviourValue::BuiltinOp {
+                    op: BuiltinOp::Pulse { length },
+                    arguments: vec![trigger],
+                },
+                vec![ty],
+            ));
+        }
+        ("pulse", _) => {
+            return Err(Error::ArgumentCount {
+                op: name.1.clone(),
+                expected: 2,
+                got: arguments.len(),
+            })
+        }
+        ("rom", [file, _]) => {
+            let file = match file {
+                syntax::Expression::Text(t) => t.clone(),
+                other => {
+                    return Err(Error::ExpectedFileName {
+                        span: other.span().clone(),
+                    })
+                }
+            };
+            let path = match &file.0 {
+                TokenData::Text(text) => text,
+                _ => unreachable!("Text-Expressions only contain Text-Tokens"),
+            };
+
+            // All the ROM-Files of the Entity are loaded before its Statements are parsed
+            let data = roms[&rom::path(&name, path)]
+                .clone()
+                .map_err(|reason| Error::RomFile {
+                    file: file.1.clone(),
+                    reason,
+                })?;
+
+            let address = arguments.into_iter().nth(1).unwrap();
+            let (address, address_span) =
+                parse_expression(address, current_header, vars, headers, roms)?;
+            let address_width = address.ty().width();
+            if address_width > MAX_ROM_ADDRESS_WIDTH {
+                return Err(Error::RomAddressTooWide {
+                    operand: address_span,
+                    width: address_width,
+                });
+            }
+            if data.words.len() > 1 << address_width {
+                return Err(Error::RomTooLarge {
+                    file: file.1,
+                    words: data.words.len(),
+                    address_width,
+                });
+            }
+
+            return Ok((
+                BehaviourValue::BuiltinOp {
+                    op: BuiltinOp::Rom {
+                        words: data.words,
+                        data_width: data.data_width,
+                    },
+                    arguments: vec![address],
+                },
+                vec![Type_::with_width(data.data_width)],
+            ));
+        }
+        ("rom", _) => {
+            return Err(Error::ArgumentCount {
+                op: name.1.clone(),
+                expected: 2,
+                got: arguments.len(),
+            })
+        }
+        _ => {}
+    };
+
+    let arguments: Vec<(Operand, Span)> = arguments
+        .into_iter()
+        .map(|a| parse_expression(a, current_header, vars, headers, roms))
+        .collect::<Result<_, _>>()?;
+
+    if op_name == "concat" {
+        let parts: Vec<_> = arguments.into_iter().map(|(a, _)| a).collect();
+        let ty = Type_::with_width(parts.iter().map(|p| p.ty().width()).sum());
+
+        return Ok((
+            BehaviourValue::Operands {
+                operands: vec![Operand::Concat(parts)],
+            },
+            vec![ty],
+        ));
+    }
+
+    let reduction = match op_name {
+        "and_reduce" => Some(BuiltinOp::And),
+        "or_reduce" => Some(BuiltinOp::Or),
+        "xor_reduce" => Some(BuiltinOp::Xor),
+        _ => None,
+    };
+    if let Some(op) = reduction {
+        check_bitwise_args(&name, &arguments, 1)?;
+        let argument = arguments.into_iter().next().map(|(a, _)| a).unwrap();
+
+        return Ok((BehaviourValue::Reduce { op, argument }, vec![Type_::Bit]));
+    }
+
+    let builtin = match op_name {
+        "and" => Some((BuiltinOp::And, 2)),
+        "not" => Some((BuiltinOp::Not, 1)),
+        "xor" => Some((BuiltinOp::Xor, 2)),
+        "or" => Some((BuiltinOp::Or, 2)),
+        "nand" => Some((BuiltinOp::Nand, 2)),
+        "nor" => Some((BuiltinOp::Nor, 2)),
+        "xnor" => Some((BuiltinOp::Xnor, 2)),
+        "dff" => Some((BuiltinOp::Dff, 2)),
+        "latch" => Some((BuiltinOp::Latch, 2)),
+        "rs_latch" => Some((BuiltinOp::RsLatch, 2)),
+        "mux" => Some((BuiltinOp::Mux, 3)),
+        "mux4" => Some((BuiltinOp::Mux4, 5)),
+        "decode" => Some((BuiltinOp::Decode, 1)),
+        "ram" => Some((BuiltinOp::Ram, 4)),
+        _ => None,
+    };
+
+    match builtin {
+        Some((op, arg_count)) => {
+            let ty = match op {
+                BuiltinOp::Dff | BuiltinOp::Latch => check_clocked_args(&name, &arguments)?,
+                BuiltinOp::Mux => check_select_args(&name, &arguments, Type_::Bit, 2)?,
+                BuiltinOp::Mux4 => check_select_args(&name, &arguments, Type_::Bits(2), 4)?,
+                BuiltinOp::Decode => {
+                    let width = check_bitwise_args(&name, &arguments, arg_count)?.width();
+                    if width > MAX_DECODE_WIDTH {
+                        return Err(Error::DecodeTooWide {
+                            operand: arguments[0].1.clone(),
+                            width,
+                        });
+                    }
+
+                    Type_::Bits(1 << width)
+                }
+                BuiltinOp::Ram => check_ram_args(&name, &arguments)?,
+                _ => check_bitwise_args(&name, &arguments, arg_count)?,
+            };
+
+            Ok((
+                BehaviourValue::BuiltinOp {
+                    op,
+                    arguments: arguments.into_iter().map(|(a, _)| a).collect(),
+                },
+                vec![ty],
+            ))
+        }
+        None => {
+            let other_header = headers.get(op_name).ok_or_else(|| Error::UnknownEntity {
+                name: name.1.clone(),
+            })?;
+
+            if arguments.len() != other_header.in_ports.len() {
+                return Err(Error::ArgumentCount {
+                    op: name.1.clone(),
+                    expected: other_header.in_ports.len(),
+                    got: arguments.len(),
+                });
+            }
+            for ((a, span), p) in arguments.iter().zip(other_header.in_ports.iter()) {
+                if a.ty() != p.ty {
+                    return Err(Error::TypeMismatch {
+                        span: span.clone(),
+                        expected: p.ty.clone(),
+                        got: a.ty(),
+                    });
+                }
+            }
+
+            let outputs: Vec<_> = other_header
+                .out_ports
+                .iter()
+                .map(|p| p.ty.clone())
+                .collect();
+
+            Ok((
+                BehaviourValue::EntityOp {
+                    op: op_name.to_string(),
+                    arguments: arguments.into_iter().map(|(a, _)| a).collect(),
+                    outputs: outputs.clone(),
+                },
+                outputs,
+            ))
+        }
+    }
+}
+
+fn parse_value(
+    value: syntax::BehaviourValue,
+    current_header: &EntityHeader,
+    vars: &HashMap<String, Variable>,
+    headers: &HashMap<String, EntityHeader>,
+    roms: &rom::Files,
+) -> Result<(BehaviourValue, Vec<Type_>), Error> {
+    // Only a Call on its own may produce more than a single Result
+    let values = match <[syntax::Expression; 1]>::try_from(value.values) {
+        Ok(
+            [syntax::Expression::Call {
+                name,
+                parameters,
+                arguments,
+                ..
+            }],
+        ) => {
+            return parse_call(
+                name,
+                parameters,
+                arguments,
+                current_header,
+                vars,
+                headers,
+                roms,
+            )
+        }
+        Ok([single]) => vec![single],
+        Err(values) => values,
+    };
+
+    let operands: Vec<_> = values
+        .into_iter()
+        .map(|v| parse_expression(v, current_header, vars, headers, roms).map(|(o, _)| o))
+        .collect::<Result<_, _>>()?;
+
+    let types: Vec<_> = operands.iter().map(|o| o.ty()).collect();
+
+    let value = BehaviourValue::Operands { operands };
+
+    Ok((value, types))
+}
+
+/// A Statement assigning Variables, together with its Position among all such Statements
+type VarStatement<'s> = (usize, &'s Vec<syntax::Target>, &'s syntax::BehaviourValue);
+
+/// Determines the Type of the first Assignment of every Variable, so that the Variables can be
+/// used before they are assigned. The Statements are resolved in the Order of their Dependencies,
+/// so every Statement is parsed once, except for the Loops of Statements depending on each other
+fn infer_variable_types(
+    statements: &[syntax::BehaviourStatement],
+    current_header: &EntityHeader,
+    headers: &HashMap<String, EntityHeader>,
+    roms: &rom::Files,
+) -> HashMap<String, Variable> {
+    let var_statements: Vec<VarStatement> = statements
+        .iter()
+        .filter_map(|stmnt| match stmnt {
+            syntax::BehaviourStatement::VarAssign { targets, value, .. } => Some((targets, value)),
+            syntax::BehaviourStatement::PortAssign { .. }
+            | syntax::BehaviourStatement::Table { .. }
+            | syntax::BehaviourStatement::For { .. }
+            | syntax::BehaviourStatement::If { .. } => None,
+        })
+        .enumerate()
+        .map(|(index, (targets, value))| (index, targets, value))
+        .collect();
+
+    // Every Variable gets its Type from the first Statement assigning it
+    let mut owners: HashMap<&str, usize> = HashMap::new();
+    for (index, targets, _) in var_statements.iter() {
+        for target in targets.iter().filter(|t| !is_discard(&t.operand)) {
+            owners
+                .entry(literal(&target.operand.name))
+                .or_insert(*index);
+        }
+    }
+
+    // Every Statement depends on the Statements assigning the Variables it uses
+    let mut edges = Vec::new();
+    let mut self_dependent = HashSet::new();
+    for (index, _, value) in var_statements.iter() {
+        let mut names = Vec::new();
+        value
+            .values
+            .iter()
+            .for_each(|v| collect_operands(v, &mut names));
+
+        for owner in names.iter().filter_map(|n| owners.get(n.as_str())) {
+            if owner == index {
+                self_dependent.insert(*index);
+            }
+            edges.push(graph::general::Edge::new(
+                *index as u32,
+                0,
+                *owner as u32,
+                0,
+            ));
+        }
+    }
+    let nodes = var_statements
+        .iter()
+        .map(|(index, _, _)| graph::general::Node::new(*index as u32, ()))
+        .collect();
+    let dependencies = graph::general::Graph::new(nodes, edges);
+
+    // Every Component only depends on the Components before it
+    let mut types: HashMap<String, Variable> = HashMap::new();
+    for mut component in dependencies.strongly_connected_components() {
+        component.sort_unstable();
+        let group: Vec<VarStatement> = component
+            .into_iter()
+            .map(|index| var_statements[index as usize])
+            .collect();
+
+        match group.as_slice() {
+            [(index, targets, value)] if !self_dependent.contains(index) => {
+                if let Ok((_, value_types)) =
+                    parse_value((*value).clone(), current_header, &types, headers, roms)
+                {
+                    assign_types(*index, targets, value_types, &owners, &mut types);
+                }
+            }
+            _ => types = infer_loop_types(&group, &owners, types, current_header, headers, roms),
+        };
+    }
+
+    types
+}
+
+/// Adds the Types of the Targets, for the Variables the Statement at the Index assigns first
+fn assign_types(
+    index: usize,
+    targets: &[syntax::Target],
+    value_types: Vec<Type_>,
+    owners: &HashMap<&str, usize>,
+    types: &mut HashMap<String, Variable>,
+) {
+    for (target, ty) in targets.iter().zip(value_types) {
+        let name = literal(&target.operand.name);
+        if is_discard(&target.operand) || owners.get(name) != Some(&index) {
+            continue;
+        }
+
+        types.entry(name.to_string()).or_insert_with(|| Variable {
+            name: name.to_string(),
+            ty,
+        });
+    }
+}
+
+/// Determines the Types of the Variables assigned in a Loop of Statements, which all depend on
+/// each other, so their Widths can't simply be derived from the Statements before them.
+///
+/// Whenever the Loop is blocked, the first Variable blocking it is assumed to have the Width of
+/// the Candidate, trying the Widths of all the known Ports and Variables, until the Types of all
+/// the Statements in the Loop are consistent. Otherwise the Types of the first Candidate are
+/// used, so the Mismatch is reported once the Statements are parsed
+fn infer_loop_types(
+    group: &[VarStatement],
+    owners: &HashMap<&str, usize>,
+    types: HashMap<String, Variable>,
+    current_header: &EntityHeader,
+    headers: &HashMap<String, EntityHeader>,
+    roms: &rom::Files,
+) -> HashMap<String, Variable> {
+    let mut known: Vec<_> = types.values().collect();
+    known.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut candidates = vec![Type_::Bit];
+    for ty in current_header
+        .in_ports
+        .iter()
+        .chain(current_header.out_ports.iter())
+        .map(|p| &p.ty)
+        .chain(known.into_iter().map(|v| &v.ty))
+    {
+        if !candidates.contains(ty) {
+            candidates.push(ty.clone());
+        }
+    }
+
+    let mut fallback = None;
+    for candidate in candidates {
+        let mut assumed = types.clone();
+
+        loop {
+            let mut progress = false;
+            let mut blocked = None;
+
+            for (index, targets, value) in group.iter() {
+                let resolved = targets
+                    .iter()
+                    .filter(|t| !is_discard(&t.operand))
+                    .all(|t| assumed.contains_key(literal(&t.operand.name)));
+                if resolved {
+                    continue;
+                }
+
+                match parse_value((*value).clone(), current_header, &assumed, headers, roms) {
+                    Ok((_, value_types)) => {
+                        let count = assumed.len();
+                        assign_types(*index, targets, value_types, owners, &mut assumed);
+                        progress |= assumed.len() > count;
+                    }
+                    Err(Error::UnknownOperand { name }) if owners.contains_key(literal(&name)) => {
+                        blocked = blocked.or_else(|| Some(literal(&name).to_string()));
+                    }
+                    // Any other Errors are reported once the Statement is actually parsed
+                    Err(_) => {}
+                };
+            }
+
+            if progress {
+                continue;
+            }
+            match blocked {
+                Some(name) => {
+                    let var = Variable {
+                        name: name.clone(),
+                        ty: candidate.clone(),
+                    };
+                    assumed.insert(name, var);
+                }
+                None => break,
+            };
+        }
+
+        let consistent = group.iter().all(|(_, targets, value)| {
+            match parse_value((*value).clone(), current_header, &assumed, headers, roms) {
+                Ok((_, value_types)) => targets.iter().zip(value_types).all(|(target, ty)| {
+                    is_discard(&target.operand)
+                        || assumed
+                            .get(literal(&target.operand.name))
+                            .map(|v| v.ty == ty)
+                            .unwrap_or(false)
+                }),
+                Err(_) => false,
+            }
+        });
+        if consistent {
+            return assumed;
+        }
+        fallback = fallback.or(Some(assumed));
+    }
+
+    // There always is at least the Candidate of a single Bit
+    fallback.unwrap()
+}
+
+/// Whether the Target is the Placeholder `_`, which discards the Result at its Position
+fn is_discard(target: &syntax::Operand) -> bool {
+    target.index.is_none() && literal(&target.name) == "_"
+}
+
+/// Parses the Target of a Port-Assignment, which needs to be an Output-Port or a Slice of one
+fn parse_port_target(
+    raw: syntax::Operand,
+    current_header: &EntityHeader,
+) -> Result<(Operand, Span), Error> {
+    let p_name = raw.name.1.clone();
+
+    parse_operand(raw, &current_header.params, |name| {
+        current_header
+            .out_ports
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| Operand::Port(p.clone()))
+    })
+    .map_err(|e| match e {
+        Error::UnknownOperand { .. } => Error::UnknownPort { name: p_name },
+        other => other,
+    })
+}
+
+fn parse_entity(
+    mut raw_entity: syntax::Entity,
+    name: &str,
+    headers: &HashMap<String, EntityHeader>,
+) -> Result<Entity, Error> {
+    let current_header = headers.get(name).unwrap().clone();
+
+    // Afterwards all the Arguments and Targets are connected by their Position
+    connections::bind(&mut raw_entity.behaviour, &current_header, headers)?;
+
+    let mut behaviour: Vec<Behaviour> = Vec::new();
+
+    // Variables can already be used before their first Assignment, which is needed to build any
+    // kind of Loop
+    let roms = rom::load_files(&raw_entity.behaviour);
+    let forward_vars = infer_variable_types(&raw_entity.behaviour, &current_header, headers, &roms);
+    let mut vars: HashMap<String, Variable> = forward_vars.clone();
+    let mut assigned: HashSet<String> = HashSet::new();
+
+    for stmnt in raw_entity.behaviour {
+        match stmnt {
+            syntax::BehaviourStatement::VarAssign {
+                targets,
+                value,
+                span,
+            } => {
+                let (b_value, value_types) =
+                    parse_value(value, &current_header, &vars, headers, &roms)?;
+
+                if value_types.len() != targets.len() {
+                    return Err(Error::TargetCount {
+                        statement: span,
+                        expected: value_types.len(),
+                        got: targets.len(),
+                    });
+                }
+
+                let target_vars: Vec<Option<Variable>> = targets
+                    .into_iter()
+                    .map(|t| t.operand)
+                    .zip(value_types)
+                    .map(|(v, ty)| {
+                        if is_discard(&v) {
+                            return Ok(None);
+                        }
+                        if v.index.is_some() {
+                            return Err(Error::SlicedVariableTarget { target: v.span });
+                        }
+
+                        let name = literal(&v.name).to_string();
+
+                        // Earlier Uses of the Variable assumed the Type of its first Assignment
+                        if let Some(forward) = forward_vars.get(&name) {
+                            if assigned.insert(name.clone()) && forward.ty != ty {
+                                return Err(Error::TypeMismatch {
+                                    span: v.span,
+                                    expected: forward.ty.clone(),
+                                    got: ty,
+                                });
+                            }
+                        }
+
+                        let var = Variable {
+                            name: name.clone(),
+                            ty,
+                        };
+
+                        vars.insert(name, var.clone());
+                        Ok(Some(var))
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                behaviour.push(Behaviour::VarAssign {
+                    targets: target_vars,
+                    value: b_value,
+                    span,
+                });
+            }
+            syntax::BehaviourStatement::PortAssign {
+                targets,
+                value,
+                span,
+            } => {
+                let (b_value, value_types) =
+                    parse_value(value, &current_header, &vars, headers, &roms)?;
+
+                if value_types.len() != targets.len() {
+                    return Err(Error::TargetCount {
+                        statement: span,
+                        expected: value_types.len(),
+                        got: targets.len(),
+                    });
+                }
+
+                let target_ports: Vec<_> = targets
+                    .into_iter()
+                    .map(|t| t.operand)
+                    .zip(value_types)
+                    .map(|(p_operand, ty)| {
+                        if is_discard(&p_operand) {
+                            return Ok(None);
+                        }
+                        let (target, span) = parse_port_target(p_operand, &current_header)?;
+
+                        if target.ty() != ty {
+                            return Err(Error::TypeMismatch {
+                                span,
+                                expected: target.ty(),
+                                got: ty,
+                            });
+                        }
+
+                        Ok(Some(target))
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                behaviour.push(Behaviour::PortAssign {
+                    targets: target_ports,
+                    value: b_value,
+                    span,
+                });
+            }
+            syntax::BehaviourStatement::Table {
+                inputs,
+                targets,
+                rows,
+                span,
+            } => {
+                let inputs_span = match (inputs.first(), inputs.last()) {
+                    (Some(first), Some(last)) => first.span().join(last.span()),
+                    _ => span.clone(),
+                };
+                let inputs: Vec<_> = inputs
+                    .into_iter()
+                    .map(|i| {
+                        parse_expression(i, &current_header, &vars, headers, &roms).map(|(o, _)| o)
+                    })
+                    .collect::<Result<_, _>>()?;
+                let input_width: u32 = inputs.iter().map(|i| i.ty().width()).sum();
+                if input_width > table::MAX_INPUTS {
+                    return Err(Error::TableTooWide {
+                        inputs: inputs_span,
+                        width: input_width,
+                    });
+                }
+
+                let targets: Vec<_> = targets
+                    .into_iter()
+                    .map(|t| parse_port_target(t, &current_header).map(|(o, _)| o))
+                    .collect::<Result<_, _>>()?;
+                let outputs: Vec<_> = targets.iter().map(|t| t.ty()).collect();
+                let output_width = outputs.iter().map(|o| o.width()).sum();
+
+                let terms = table::parse_rows(&rows, input_width, output_width)?;
+
+                behaviour.push(Behaviour::PortAssign {
+                    targets: targets.into_iter().map(Some).collect(),
+                    value: BehaviourValue::Table {
+                        inputs,
+                        outputs,
+                        terms,
+                    },
+                    span,
+                });
+            }
+            syntax::BehaviourStatement::For { .. } | syntax::BehaviourStatement::If { .. } => {
+                unreachable!("Generate-Blocks are already elaborated")
+            }
+        };
+    }
+
+    Ok(Entity {
+        name: current_header.name,
+        in_ports: current_header.in_ports,
+        out_ports: current_header.out_ports,
+        behaviour,
+    })
+}
+
+impl Operand {
+    pub fn ty(&self) -> Type_ {
+        match self {
+            Self::Port(p) => p.ty.clone(),
+            Self::Variable(v) => v.ty.clone(),
+            Self::Slice { range, .. } => Type_::with_width(range.end - range.start),
+            Self::Concat(parts) => Type_::with_width(parts.iter().map(|p| p.ty().width()).sum()),
+            Self::Constant(bits) => Type_::with_width(bits.len() as u32),
+            Self::Value { ty, .. } => ty.clone(),
+        }
+    }
+
+    /// Returns the Sources of all the Bits of the Operand, starting with the least significant
+    /// Bit, generating the Nodes for any nested Values
+    fn bits(&self, builder: &mut GraphBuilder) -> Vec<(u32, u32)> {
+        match self {
+            Self::Port(p) => {
+                let ids = builder.in_ports.get(&p.name).unwrap();
+                ids.iter().map(|id| (*id, 0)).collect()
+            }
+            Self::Variable(v) => {
+                // A Variable used before its first Assignment gets its Nodes right away, which
+                // are then connected once the Variable is assigned
+                if !builder.var_ids.contains_key(&v.name) {
+                    let ids: Vec<_> = (0..v.ty.width())
+                        .map(|bit| {
+                            builder.add_node(graph::entity::NodeType::Variable {
+                                name: bit_name(&v.name, &v.ty, bit),
+                            })
+                        })
+                        .collect();
+                    builder.forward_vars.insert(v.name.clone(), ids.clone());
+                    builder.var_ids.insert(v.name.clone(), ids);
+                }
+
+                let ids = builder.var_ids.get(&v.name).unwrap();
+                ids.iter().map(|id| (*id, 0)).collect()
+            }
+            Self::Slice { inner, range } => {
+                let inner_bits = inner.bits(builder);
+                inner_bits[range.start as usize..range.end as usize].to_vec()
+            }
+            Self::Concat(parts) => parts.iter().rev().flat_map(|p| p.bits(builder)).collect(),
+            Self::Constant(bits) => bits
+                .iter()
+                .map(|value| {
+                    let id = builder.add_node(graph::entity::NodeType::Constant { value: *value });
+                    (id, 0)
+                })
+                .collect(),
+            Self::Value { value, .. } => value.to_graph(builder).remove(0),
+        }
+    }
+
+    fn used_entities(&self, names: &mut Vec<String>) {
+        match self {
+            Self::Slice { inner, .. } => inner.used_entities(names),
+            Self::Concat(parts) => parts.iter().for_each(|p| p.used_entities(names)),
+            Self::Value { value, .. } => value.used_entities(names),
+            Self::Variable(_) | Self::Port(_) | Self::Constant(_) => {}
+        }
+    }
+
+    /// Returns the IDs of the Output-Nodes for all the Bits of the Operand, when it is used as
+    /// the Target of a Port-Assignment
+    fn target_bits(&self, out_ports: &HashMap<String, Vec<u32>>) -> Vec<u32> {
+        match self {
+            Self::Port(p) => out_ports.get(&p.name).unwrap().clone(),
+            Self::Slice { inner, range } => {
+                let inner_bits = inner.target_bits(out_ports);
+                inner_bits[range.start as usize..range.end as usize].to_vec()
+            }
+            other => panic!("Invalid Port-Target: {:?}", other),
+        }
+    }
+}
+
+impl BehaviourValue {
+    /// Collects the Names of all the Entities used in the Value, including nested Values
+    fn used_entities(&self, names: &mut Vec<String>) {
+        match self {
+            Self::BuiltinOp { arguments, .. } => {
+                arguments.iter().for_each(|a| a.used_entities(names))
+            }
+            Self::Reduce { argument, .. } => argument.used_entities(names),
+            Self::EntityOp { op, arguments, .. } => {
+                names.push(op.clone());
+                arguments.iter().for_each(|a| a.used_entities(names))
+            }
+            Self::Operands { operands } => operands.iter().for_each(|o| o.used_entities(names)),
+            Self::Clock { .. } => {}
+            Self::Table { inputs, .. } => inputs.iter().for_each(|i| i.used_entities(names)),
+        }
+    }
+}
+
+impl BuiltinOp {
+    fn graph_op(&self) -> graph::entity::BuiltinOp {
+        match self {
+            Self::And => graph::entity::BuiltinOp::And,
+            Self::Xor => graph::entity::BuiltinOp::Xor,
+            Self::Not => graph::entity::BuiltinOp::Not,
+            Self::Or => graph::entity::BuiltinOp::Or,
+            Self::Nand => graph::entity::BuiltinOp::Nand,
+            Self::Nor => graph::entity::BuiltinOp::Nor,
+            Self::Xnor => graph::entity::BuiltinOp::Xnor,
+            Self::Dff => graph::entity::BuiltinOp::Dff,
+            Self::Latch => graph::entity::BuiltinOp::Latch,
+            Self::RsLatch => graph::entity::BuiltinOp::RsLatch,
+            Self::Pulse { length } => graph::entity::BuiltinOp::Pulse { length: *length },
+            Self::Mux
+            | Self::Mux4
+            | Self::Decode
+            | Self::Add
+            | Self::Sub
+            | Self::Eq
+            | Self::Less => unreachable!("{:?} is lowered to a Network of Gates", self),
+            Self::Rom { .. } => unreachable!("ROMs need to know the Width of their Address"),
+            Self::Ram => unreachable!("RAMs need to know the Widths of their Address and Data"),
+        }
+    }
+}
+
+/// The Name of the Graph-Node for a single Bit of a Port or Variable
+fn bit_name(name: &str, ty: &Type_, bit: u32) -> String {
+    match ty {
+        Type_::Bit => name.to_string(),
+        Type_::Bits(_) => format!("{}[{}]", name, bit),
+    }
+}
+
+/// The State while generating the Graph for an Entity
+struct GraphBuilder {
+    next_id: u32,
+    nodes: Vec<graph::entity::Node>,
+    edges: Vec<graph::entity::Edge>,
+    /// The Nodes for every Bit of the Input-Ports
+    in_ports: HashMap<String, Vec<u32>>,
+    /// The Nodes for every Bit of the Variables assigned so far
+    var_ids: HashMap<String, Vec<u32>>,
+    /// The Nodes of the Variables, that have been used before their first Assignment
+    forward_vars: HashMap<String, Vec<u32>>,
+    adder: AdderKind,
+}
+
+impl GraphBuilder {
+    fn add_node(&mut self, ty: graph::entity::NodeType) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.nodes.push(graph::entity::Node::new(id, ty));
+        id
+    }
+
+    fn add_edge(&mut self, (src_id, src_port): (u32, u32), dest_id: u32, dest_port: u32) {
+        self.edges.push(graph::entity::Edge::new(
+            src_id, src_port, dest_id, dest_port,
+        ));
+    }
+
+    /// Adds a single Gate, connected to the given Inputs, returning its Output
+    fn gate(&mut self, op: graph::entity::BuiltinOp, inputs: &[(u32, u32)]) -> (u32, u32) {
+        let node_id = self.add_node(graph::entity::NodeType::BuiltinOp { op });
+        for (index, src) in inputs.iter().enumerate() {
+            self.add_edge(*src, node_id, index as u32);
+        }
+
+        (node_id, 0)
+    }
+}
+
+impl BehaviourValue {
+    /// Generates the Nodes and Edges for this Value, returning the Sources for every Bit of every
+    /// Result of the Value
+    fn to_graph(&self, builder: &mut GraphBuilder) -> Vec<Vec<(u32, u32)>> {
+        match self {
+            Self::BuiltinOp {
+                op: BuiltinOp::Mux,
+                arguments,
+            } => {
+                let arg_bits: Vec<_> = arguments.iter().map(|a| a.bits(builder)).collect();
+
+                let sel = arg_bits[0][0];
+                let not_sel = builder.gate(graph::entity::BuiltinOp::Not, &[sel]);
+
+                vec![builder.mux((sel, not_sel), &arg_bits[1], &arg_bits[2])]
+            }
+            // A Tree of two Levels of Muxes, where the lower Select-Bit picks within the Pairs
+            Self::BuiltinOp {
+                op: BuiltinOp::Mux4,
+                arguments,
+            } => {
+                let arg_bits: Vec<_> = arguments.iter().map(|a| a.bits(builder)).collect();
+
+                let sel_bits: Vec<_> = arg_bits[0]
+                    .iter()
+                    .map(|sel| (*sel, builder.gate(graph::entity::BuiltinOp::Not, &[*sel])))
+                    .collect();
+
+                let low = builder.mux(sel_bits[0], &arg_bits[1], &arg_bits[2]);
+                let high = builder.mux(sel_bits[0], &arg_bits[3], &arg_bits[4]);
+                vec![builder.mux(sel_bits[1], &low, &high)]
+            }
+            // Every Bit of the Result is the And of all the Bits of the Argument, where the Bits
+            // that are off in its Index are inverted first
+            Self::BuiltinOp {
+                op: BuiltinOp::Decode,
+                arguments,
+            } => {
+                let bits = arguments[0].bits(builder);
+                let inverted: Vec<_> = bits
+                    .iter()
+                    .map(|b| builder.gate(graph::entity::BuiltinOp::Not, &[*b]))
+                    .collect();
+
+                let outputs = (0..1u32 << bits.len())
+                    .map(|index| {
+                        let literals = (0..bits.len())
+                            .map(|bit| {
+                                if index & (1 << bit) != 0 {
+                                    bits[bit]
+                                } else {
+                                    inverted[bit]
+                                }
+                            })
+                            .collect();
+                        builder.reduce(graph::entity::BuiltinOp::And, literals)
+                    })
                     .collect();
 
-                let outputs: Vec<_> = (0..*port_count).map(|p| (node_id, p)).collect();
-                (Some(node), edges, outputs)
+                vec![outputs]
+            }
+            Self::BuiltinOp {
+                op: op @ (BuiltinOp::Add | BuiltinOp::Sub | BuiltinOp::Eq | BuiltinOp::Less),
+                arguments,
+            } => {
+                let a = arguments[0].bits(builder);
+                let b = arguments[1].bits(builder);
+
+                vec![builder.arithmetic(op, &a, &b)]
+            }
+            Self::BuiltinOp {
+                op: BuiltinOp::Rom { words, data_width },
+                arguments,
+            } => {
+                let address = arguments[0].bits(builder);
+
+                let op = graph::entity::BuiltinOp::Rom {
+                    address_width: address.len() as u32,
+                    data_width: *data_width,
+                    words: words.clone(),
+                };
+                let node_id = builder.add_node(graph::entity::NodeType::BuiltinOp { op });
+                for (index, src) in address.into_iter().enumerate() {
+                    builder.add_edge(src, node_id, index as u32);
+                }
+
+                vec![(0..*data_width).map(|bit| (node_id, bit)).collect()]
+            }
+            Self::BuiltinOp {
+                op: BuiltinOp::Ram,
+                arguments,
+            } => {
+                let address = arguments[0].bits(builder);
+                let data = arguments[1].bits(builder);
+
+                let data_width = data.len() as u32;
+
+                let op = graph::entity::BuiltinOp::Ram {
+                    address_width: address.len() as u32,
+                    data_width,
+                };
+                let node_id = builder.add_node(graph::entity::NodeType::BuiltinOp { op });
+
+                // The Ports are the Bits of the Address, then of the Data, then the Write-Enable
+                // and the Clock
+                let inputs = address
+                    .into_iter()
+                    .chain(data)
+                    .chain(arguments[2].bits(builder))
+                    .chain(arguments[3].bits(builder));
+                for (index, src) in inputs.enumerate() {
+                    builder.add_edge(src, node_id, index as u32);
+                }
+
+                vec![(0..data_width).map(|bit| (node_id, bit)).collect()]
             }
-            Self::Variables { vars } => {
-                let srcs: Vec<_> = vars
+            Self::BuiltinOp { op, arguments } => {
+                let arg_bits: Vec<_> = arguments.iter().map(|a| a.bits(builder)).collect();
+
+                // Every Bit of the Arguments gets its own Operation
+                let mut sources = Vec::new();
+                for bit in 0..arg_bits[0].len() {
+                    let node_id =
+                        builder.add_node(graph::entity::NodeType::BuiltinOp { op: op.graph_op() });
+
+                    // Single Bit Arguments, like a Clock, are shared by all the Bits
+                    for (index, bits) in arg_bits.iter().enumerate() {
+                        let src = if bits.len() == 1 { bits[0] } else { bits[bit] };
+                        builder.add_edge(src, node_id, index as u32);
+                    }
+
+                    sources.push((node_id, 0));
+                }
+
+                vec![sources]
+            }
+            Self::Reduce { op, argument } => {
+                let bits = argument.bits(builder);
+                vec![vec![builder.reduce(op.graph_op(), bits)]]
+            }
+            Self::EntityOp {
+                op,
+                arguments,
+                outputs,
+            } => {
+                let arg_bits: Vec<_> = arguments.iter().flat_map(|a| a.bits(builder)).collect();
+
+                let node_id =
+                    builder.add_node(graph::entity::NodeType::EntityOp { name: op.clone() });
+
+                // Every Bit of every Argument is connected to its own Port of the Entity
+                for (index, src) in arg_bits.into_iter().enumerate() {
+                    builder.add_edge(src, node_id, index as u32);
+                }
+
+                let mut port = 0;
+                outputs
                     .iter()
-                    .map(|v| {
-                        let v_id = var_ids.get(&v.name).unwrap();
-                        (*v_id, 0)
+                    .map(|ty| {
+                        let bits: Vec<_> =
+                            (port..port + ty.width()).map(|p| (node_id, p)).collect();
+                        port += ty.width();
+                        bits
                     })
+                    .collect()
+            }
+            Self::Operands { operands } => operands.iter().map(|o| o.bits(builder)).collect(),
+            Self::Clock { period } => {
+                let node_id = builder.add_node(graph::entity::NodeType::Clock { period: *period });
+                vec![vec![(node_id, 0)]]
+            }
+            Self::Table {
+                inputs,
+                outputs,
+                terms,
+            } => {
+                // The Columns of the Table start with the most significant Bit of every Input
+                let columns: Vec<_> = inputs
+                    .iter()
+                    .flat_map(|i| i.bits(builder).into_iter().rev())
                     .collect();
+                let mut bits = builder.table(&columns, terms).into_iter();
 
-                (None, Vec::new(), srcs)
+                outputs
+                    .iter()
+                    .map(|ty| {
+                        let mut target: Vec<_> = bits.by_ref().take(ty.width() as usize).collect();
+                        target.reverse();
+                        target
+                    })
+                    .collect()
             }
         }
     }
 }
 
 impl Entity {
-    pub fn graph(&self) -> graph::entity::Graph {
-        let mut id = 0;
-
-        let mut get_id = || {
-            let tmp = id;
-            id += 1;
-            tmp
+    pub fn graph(&self, options: &Options) -> graph::entity::Graph {
+        let mut builder = GraphBuilder {
+            next_id: 0,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            in_ports: HashMap::new(),
+            var_ids: HashMap::new(),
+            forward_vars: HashMap::new(),
+            adder: options.adder.clone(),
         };
 
-        let (input_nodes, in_ports): (Vec<_>, HashMap<_, _>) = self
-            .in_ports
-            .iter()
-            .enumerate()
-            .map(|(index, port)| {
-                let id = get_id();
-                (
-                    graph::entity::Node::new(
-                        id,
-                        graph::entity::NodeType::Input {
-                            name: port.name.to_string(),
-                            number: index as u32,
-                        },
-                    ),
-                    (port.name.to_string(), id),
-                )
-            })
-            .unzip();
-        let (output_nodes, out_ports): (Vec<_>, HashMap<_, _>) = self
-            .out_ports
-            .iter()
-            .enumerate()
-            .map(|(index, port)| {
-                let id = get_id();
-                (
-                    graph::entity::Node::new(
-                        id,
-                        graph::entity::NodeType::Output {
-                            name: port.name.to_string(),
-                            number: index as u32,
-                        },
-                    ),
-                    (port.name.to_string(), id),
-                )
-            })
-            .unzip();
+        // Every Port is split into one Node per Bit, which are numbered consecutively across all
+        // the Ports
+        let mut number = 0;
+        for port in self.in_ports.iter() {
+            let ids: Vec<_> = (0..port.ty.width())
+                .map(|bit| {
+                    number += 1;
+                    builder.add_node(graph::entity::NodeType::Input {
+                        name: bit_name(&port.name, &port.ty, bit),
+                        number: number - 1,
+                    })
+                })
+                .collect();
+            builder.in_ports.insert(port.name.to_string(), ids);
+        }
+        let mut number = 0;
+        let mut out_ports = HashMap::new();
+        for port in self.out_ports.iter() {
+            let ids: Vec<_> = (0..port.ty.width())
+                .map(|bit| {
+                    number += 1;
+                    builder.add_node(graph::entity::NodeType::Output {
+                        name: bit_name(&port.name, &port.ty, bit),
+                        number: number - 1,
+                    })
+                })
+                .collect();
+            out_ports.insert(port.name.to_string(), ids);
+        }
 
-        let mut var_nodes = HashMap::new();
-        let mut b_edges = Vec::new();
-        let mut b_nodes = Vec::new();
         for stmnt in self.behaviour.iter() {
             match stmnt {
-                Behaviour::VarAssign { targets, value } => {
-                    let node_id = get_id();
+                Behaviour::VarAssign { targets, value, .. } => {
+                    let outputs = value.to_graph(&mut builder);
+
+                    for (var, srcs) in targets.iter().zip(outputs) {
+                        // Nothing is connected to the discarded Results, so the Logic only
+                        // producing them is removed once the Entities are inlined
+                        let var = match var {
+                            Some(v) => v,
+                            None => continue,
+                        };
+                        if let Some(var_ids) = builder.forward_vars.remove(&var.name) {
+                            for (src, var_id) in srcs.into_iter().zip(var_ids.iter()) {
+                                builder.add_edge(src, *var_id, 0);
+                            }
+                            builder.var_ids.insert(var.name.clone(), var_ids);
+                            continue;
+                        }
 
-                    let (n_node, n_edges, outputs) = value.to_graph(node_id, &in_ports, &var_nodes);
-                    if let Some(n) = n_node {
-                        b_nodes.push(n);
-                    }
-                    b_edges.extend(n_edges);
-
-                    for (var, src) in targets.iter().zip(outputs.iter()) {
-                        let var_id = get_id();
-                        var_nodes.insert(var.name.clone(), var_id);
-
-                        b_nodes.push(graph::entity::Node::new(
-                            var_id,
-                            graph::entity::NodeType::Variable {
-                                name: var.name.clone(),
-                            },
-                        ));
-                        b_edges.push(graph::entity::Edge::new(src.0, src.1, var_id, 0));
+                        let var_ids: Vec<_> = srcs
+                            .into_iter()
+                            .enumerate()
+                            .map(|(bit, src)| {
+                                let var_id = builder.add_node(graph::entity::NodeType::Variable {
+                                    name: bit_name(&var.name, &var.ty, bit as u32),
+                                });
+                                builder.add_edge(src, var_id, 0);
+                                var_id
+                            })
+                            .collect();
+                        builder.var_ids.insert(var.name.clone(), var_ids);
                     }
                 }
-                Behaviour::PortAssign { targets, value } => {
-                    let node_id = get_id();
+                Behaviour::PortAssign { targets, value, .. } => {
+                    let outputs = value.to_graph(&mut builder);
 
-                    let (n_node, n_edges, outputs) = value.to_graph(node_id, &in_ports, &var_nodes);
-                    if let Some(n) = n_node {
-                        b_nodes.push(n);
-                    }
-                    b_edges.extend(n_edges);
-
-                    for (target, src) in targets.iter().zip(outputs.iter()) {
-                        let port_id = out_ports.get(&target.name).unwrap();
+                    for (target, srcs) in targets.iter().zip(outputs) {
+                        let target = match target {
+                            Some(t) => t,
+                            None => continue,
+                        };
+                        let port_bits = target.target_bits(&out_ports);
 
-                        b_edges.push(graph::entity::Edge::new(src.0, src.1, *port_id, 0));
+                        for (src, port_id) in srcs.into_iter().zip(port_bits) {
+                            builder.add_edge(src, port_id, 0);
+                        }
                     }
                 }
             };
         }
 
-        let nodes: Vec<_> = input_nodes
+        graph::entity::Graph::new(builder.nodes, builder.edges)
+    }
+
+    /// Finds the Loops in the given Graph of this Entity, that do not pass through any Storage,
+    /// so their Value never settles. Loops through other Entities are skipped, as they can only
+    /// be judged once the Entities are inlined. Every Loop is described by the Names of its
+    /// Variables, the Statements assigning them and whether it contains any Logic
+    fn combinational_loops(
+        &self,
+        graph: &graph::entity::Graph,
+    ) -> Vec<(Vec<String>, Vec<Span>, bool)> {
+        let mut var_statements: HashMap<&str, usize> = HashMap::new();
+        for (index, stmnt) in self.behaviour.iter().enumerate() {
+            if let Behaviour::VarAssign { targets, .. } = stmnt {
+                for var in targets.iter().flatten() {
+                    var_statements.entry(var.name.as_str()).or_insert(index);
+                }
+            }
+        }
+
+        let mut loops: Vec<(Vec<usize>, Vec<String>, bool)> = Vec::new();
+        for component in graph.strongly_connected_components() {
+            let is_loop = component.len() > 1
+                || graph
+                    .edges
+                    .iter()
+                    .any(|e| e.src_id == component[0] && e.dest_id == component[0]);
+            if !is_loop {
+                continue;
+            }
+
+            let nodes: Vec<_> = component
+                .iter()
+                .filter_map(|id| graph.get_node(*id).map(|n| &n.inner))
+                .collect();
+            if nodes
+                .iter()
+                .any(|n| n.is_storage() || matches!(n, graph::entity::NodeType::EntityOp { .. }))
+            {
+                continue;
+            }
+
+            let mut names = Vec::new();
+            let mut logic = false;
+            for node in nodes {
+                match node {
+                    graph::entity::NodeType::Variable { name } => {
+                        // The Loops of Buses are reported once for the whole Variable
+                        let name = name.split('[').next().unwrap_or(name);
+                        names.push(name.to_string());
+                    }
+                    _ => logic = true,
+                };
+            }
+
+            let mut statements: Vec<_> = names
+                .iter()
+                .filter_map(|name| var_statements.get(name.as_str()).copied())
+                .collect();
+            statements.sort_unstable();
+            statements.dedup();
+            names.sort();
+            names.dedup();
+
+            if !loops.iter().any(|(s, _, _)| s == &statements) {
+                loops.push((statements, names, logic));
+            }
+        }
+
+        loops
             .into_iter()
-            .chain(output_nodes.into_iter())
-            .chain(b_nodes.into_iter())
-            .collect();
+            .map(|(statements, names, logic)| {
+                let spans = statements
+                    .into_iter()
+                    .map(|index| self.behaviour[index].span().clone())
+                    .collect();
+                (names, spans, logic)
+            })
+            .collect()
+    }
+
+    /// Returns the Names of all the Entities used by this Entity, together with the first
+    /// Statement using each of them
+    fn used_entities(&self) -> Vec<(String, Span)> {
+        let mut result: Vec<(String, Span)> = Vec::new();
 
-        graph::entity::Graph::new(nodes, b_edges)
+        for stmnt in self.behaviour.iter() {
+            let mut names = Vec::new();
+            stmnt.value().used_entities(&mut names);
+
+            for name in names {
+                if !result.iter().any(|(n, _)| n == &name) {
+                    result.push((name, stmnt.span().clone()));
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl Behaviour {
+    fn value(&self) -> &BehaviourValue {
+        match self {
+            Self::VarAssign { value, .. } | Self::PortAssign { value, .. } => value,
+        }
+    }
+
+    fn span(&self) -> &Span {
+        match self {
+            Self::VarAssign { span, .. } | Self::PortAssign { span, .. } => span,
+        }
     }
 }
@@ -0,0 +1,190 @@
+use crate::graph;
+
+use super::{AdderKind, BuiltinOp, GraphBuilder};
+
+impl GraphBuilder {
+    /// Selects `a` if `sel` is off and `b` otherwise, for every Bit of the Inputs, where `not_sel`
+    /// is the already inverted Select-Bit
+    pub fn mux(
+        &mut self,
+        (sel, not_sel): ((u32, u32), (u32, u32)),
+        a: &[(u32, u32)],
+        b: &[(u32, u32)],
+    ) -> Vec<(u32, u32)> {
+        a.iter()
+            .zip(b.iter())
+            .map(|(a, b)| {
+                let first = self.gate(graph::entity::BuiltinOp::And, &[*a, not_sel]);
+                let second = self.gate(graph::entity::BuiltinOp::And, &[*b, sel]);
+                self.gate(graph::entity::BuiltinOp::Or, &[first, second])
+            })
+            .collect()
+    }
+
+    /// Combines all the Sources into a single Bit, combining neighbouring Bits level by level,
+    /// which results in a balanced Tree of Operations
+    pub fn reduce(
+        &mut self,
+        op: graph::entity::BuiltinOp,
+        mut level: Vec<(u32, u32)>,
+    ) -> (u32, u32) {
+        while level.len() > 1 {
+            let mut next_level = Vec::with_capacity(level.len() / 2 + 1);
+
+            for pair in level.chunks(2) {
+                if let [first, second] = pair {
+                    next_level.push(self.gate(op.clone(), &[*first, *second]));
+                } else {
+                    next_level.extend_from_slice(pair);
+                }
+            }
+
+            level = next_level;
+        }
+
+        level[0]
+    }
+
+    /// Adds the two Buses of the same Width, returning the Bits of the Sum and the final Carry
+    pub fn add(
+        &mut self,
+        a: &[(u32, u32)],
+        b: &[(u32, u32)],
+        carry_in: Option<(u32, u32)>,
+    ) -> (Vec<(u32, u32)>, (u32, u32)) {
+        use graph::entity::BuiltinOp::{And, Or, Xor};
+
+        // Whether each Bit generates a Carry on its own and whether it propagates an incoming one
+        let generate: Vec<_> = a
+            .iter()
+            .zip(b.iter())
+            .map(|(a, b)| self.gate(And, &[*a, *b]))
+            .collect();
+        let propagate: Vec<_> = a
+            .iter()
+            .zip(b.iter())
+            .map(|(a, b)| self.gate(Xor, &[*a, *b]))
+            .collect();
+
+        // The Carries into every Bit, followed by the final Carry
+        let carries: Vec<Option<(u32, u32)>> = match self.adder {
+            AdderKind::Ripple => {
+                let mut carries = vec![carry_in];
+                for (g, p) in generate.iter().zip(propagate.iter()) {
+                    let carry = match carries.last().unwrap() {
+                        Some(c) => {
+                            let passed = self.gate(And, &[*p, *c]);
+                            self.gate(Or, &[*g, passed])
+                        }
+                        None => *g,
+                    };
+                    carries.push(Some(carry));
+                }
+                carries
+            }
+            // A Kogge-Stone Prefix-Tree, where every Level combines each Group of Bits with the
+            // Group right below it, so the Carries are known after a logarithmic Number of Levels
+            AdderKind::Lookahead => {
+                // The Generate-Signal of each Group and its Propagate-Signal, unless the Group
+                // already reaches down to the Carry-In
+                type Group = ((u32, u32), Option<(u32, u32)>);
+
+                let mut groups: Vec<Group> = carry_in
+                    .map(|c| (c, None))
+                    .into_iter()
+                    .chain(
+                        generate
+                            .iter()
+                            .copied()
+                            .zip(propagate.iter().copied().map(Some)),
+                    )
+                    .collect();
+
+                let mut distance = 1;
+                while distance < groups.len() {
+                    let previous = groups.clone();
+
+                    for index in distance..groups.len() {
+                        let (g_high, p_high) = previous[index];
+                        let (g_low, p_low) = previous[index - distance];
+
+                        let g = match p_high {
+                            Some(p) => {
+                                let passed = self.gate(And, &[p, g_low]);
+                                self.gate(Or, &[g_high, passed])
+                            }
+                            None => g_high,
+                        };
+                        // Only Groups that reach far enough are combined again on the next Level
+                        let p = match (p_high, p_low) {
+                            (Some(high), Some(low)) if index >= 2 * distance => {
+                                Some(self.gate(And, &[high, low]))
+                            }
+                            _ => None,
+                        };
+                        groups[index] = (g, p);
+                    }
+
+                    distance *= 2;
+                }
+
+                let mut carries = match carry_in {
+                    Some(_) => Vec::new(),
+                    None => vec![None],
+                };
+                carries.extend(groups.into_iter().map(|(g, _)| Some(g)));
+                carries
+            }
+        };
+
+        let sum = propagate
+            .iter()
+            .zip(carries.iter())
+            .map(|(p, carry)| match carry {
+                Some(c) => self.gate(Xor, &[*p, *c]),
+                None => *p,
+            })
+            .collect();
+
+        (sum, carries.last().unwrap().unwrap())
+    }
+
+    /// Subtracts `b` from `a`, by adding the inverted `b` plus one, returning the Bits of the
+    /// Difference and the final Carry, which is off if `a` is smaller than `b`
+    pub fn sub(&mut self, a: &[(u32, u32)], b: &[(u32, u32)]) -> (Vec<(u32, u32)>, (u32, u32)) {
+        let inverted: Vec<_> = b
+            .iter()
+            .map(|b| self.gate(graph::entity::BuiltinOp::Not, &[*b]))
+            .collect();
+        let one = self.add_node(graph::entity::NodeType::Constant { value: true });
+
+        self.add(a, &inverted, Some((one, 0)))
+    }
+
+    /// Computes the Result of the Arithmetic or Comparison on the two Buses of the same Width
+    pub fn arithmetic(
+        &mut self,
+        op: &BuiltinOp,
+        a: &[(u32, u32)],
+        b: &[(u32, u32)],
+    ) -> Vec<(u32, u32)> {
+        match op {
+            BuiltinOp::Add => self.add(a, b, None).0,
+            BuiltinOp::Sub => self.sub(a, b).0,
+            BuiltinOp::Eq => {
+                let equal = a
+                    .iter()
+                    .zip(b.iter())
+                    .map(|(a, b)| self.gate(graph::entity::BuiltinOp::Xnor, &[*a, *b]))
+                    .collect();
+                vec![self.reduce(graph::entity::BuiltinOp::And, equal)]
+            }
+            // `a - b` only borrows, if `a` is smaller than `b`
+            BuiltinOp::Less => {
+                let (_, carry) = self.sub(a, b);
+                vec![self.gate(graph::entity::BuiltinOp::Not, &[carry])]
+            }
+            _ => unreachable!(),
+        }
+    }
+}
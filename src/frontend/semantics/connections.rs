@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use super::{call_name, literal, syntax, EntityHeader, Error, Port, Span, Token};
+
+/// Connects the named Arguments and Targets of all the Calls of Entities in the Statements to the
+/// Ports with the same Name, so that afterwards all of them are connected by their Position
+pub fn bind(
+    statements: &mut [syntax::BehaviourStatement],
+    current_header: &EntityHeader,
+    headers: &HashMap<String, EntityHeader>,
+) -> Result<(), Error> {
+    for stmnt in statements.iter_mut() {
+        match stmnt {
+            syntax::BehaviourStatement::PortAssign {
+                targets,
+                value,
+                span,
+            }
+            | syntax::BehaviourStatement::VarAssign {
+                targets,
+                value,
+                span,
+            } => {
+                for v in value.values.iter_mut() {
+                    bind_arguments(v, current_header, headers)?;
+                }
+                bind_targets(targets, value, span, current_header, headers)?;
+            }
+            syntax::BehaviourStatement::Table { inputs, .. } => {
+                for i in inputs.iter_mut() {
+                    bind_arguments(i, current_header, headers)?;
+                }
+            }
+            syntax::BehaviourStatement::For { .. } | syntax::BehaviourStatement::If { .. } => {
+                unreachable!("Generate-Blocks are already elaborated")
+            }
+        };
+    }
+
+    Ok(())
+}
+
+/// Orders the named Arguments of the Calls of Entities in the Expression, including nested ones
+fn bind_arguments(
+    expression: &mut syntax::Expression,
+    current_header: &EntityHeader,
+    headers: &HashMap<String, EntityHeader>,
+) -> Result<(), Error> {
+    match expression {
+        syntax::Expression::Call {
+            name,
+            parameters,
+            arguments,
+            span,
+        } => {
+            for a in arguments.iter_mut() {
+                bind_arguments(&mut a.value, current_header, headers)?;
+            }
+
+            if arguments.iter().all(|a| a.port.is_none()) {
+                return Ok(());
+            }
+            // Named Arguments of anything else are reported together with the other Errors of
+            // the Call
+            let header = match headers.get(&call_name(name, parameters, current_header)?) {
+                Some(h) => h,
+                None => return Ok(()),
+            };
+
+            let connections = std::mem::take(arguments)
+                .into_iter()
+                .map(|a| (a.port.clone(), a.span(), a))
+                .collect();
+            let ordered = order(
+                connections,
+                &header.in_ports,
+                &header.name,
+                false,
+                span,
+                |got| Error::ArgumentCount {
+                    op: name.1.clone(),
+                    expected: header.in_ports.len(),
+                    got,
+                },
+            )?;
+
+            *arguments = ordered
+                .into_iter()
+                .map(|a| syntax::Argument {
+                    port: None,
+                    value: a.value,
+                })
+                .collect();
+        }
+        syntax::Expression::Not { inner, .. } => bind_arguments(inner, current_header, headers)?,
+        syntax::Expression::Binary { left, right, .. } => {
+            bind_arguments(left, current_header, headers)?;
+            bind_arguments(right, current_header, headers)?;
+        }
+        syntax::Expression::Operand(_) | syntax::Expression::Text(_) => {}
+    };
+
+    Ok(())
+}
+
+/// Orders the named Targets by the Outputs of the Entity, whose Call is the Value
+fn bind_targets(
+    targets: &mut Vec<syntax::Target>,
+    value: &syntax::BehaviourValue,
+    statement: &Span,
+    current_header: &EntityHeader,
+    headers: &HashMap<String, EntityHeader>,
+) -> Result<(), Error> {
+    let port = match targets.iter().find_map(|t| t.port.as_ref()) {
+        Some(p) => p,
+        None => return Ok(()),
+    };
+
+    let header = match value.values.as_slice() {
+        [syntax::Expression::Call {
+            name, parameters, ..
+        }] => headers.get(&call_name(name, parameters, current_header)?),
+        _ => None,
+    };
+    let header = header.ok_or_else(|| Error::NamedTarget {
+        port: port.1.clone(),
+    })?;
+
+    let connections = std::mem::take(targets)
+        .into_iter()
+        .map(|t| {
+            let span = match &t.port {
+                Some(port) => port.1.join(&t.operand.span),
+                None => t.operand.span.clone(),
+            };
+            (t.port.clone(), span, t)
+        })
+        .collect();
+    let ordered = order(
+        connections,
+        &header.out_ports,
+        &header.name,
+        true,
+        statement,
+        |got| Error::TargetCount {
+            statement: statement.clone(),
+            expected: header.out_ports.len(),
+            got,
+        },
+    )?;
+
+    *targets = ordered
+        .into_iter()
+        .map(|t| syntax::Target {
+            port: None,
+            operand: t.operand,
+        })
+        .collect();
+
+    Ok(())
+}
+
+/// Orders the Connections by the Ports they are connected to, where the Connections without a
+/// Name are connected to the first Ports in Order. Every Connection has the Name of its Port and
+/// the Span of the whole Connection, while `too_many` creates the Error for more Connections by
+/// Position than there are Ports from the total Number of Connections
+fn order<T, F>(
+    connections: Vec<(Option<Token>, Span, T)>,
+    ports: &[Port],
+    entity: &str,
+    output: bool,
+    call: &Span,
+    too_many: F,
+) -> Result<Vec<T>, Error>
+where
+    F: FnOnce(usize) -> Error,
+{
+    let count = connections.len();
+    let mut slots: Vec<Option<(Span, T)>> = ports.iter().map(|_| None).collect();
+
+    let mut named = false;
+    for (position, (port, span, connection)) in connections.into_iter().enumerate() {
+        let index = match port {
+            None if named => return Err(Error::PositionalConnection { connection: span }),
+            None => position,
+            Some(port) => {
+                named = true;
+                let index = ports.iter().position(|p| p.name == literal(&port));
+                let index = index.ok_or_else(|| Error::UnknownConnection {
+                    port: port.1.clone(),
+                    entity: entity.to_string(),
+                    output,
+                })?;
+
+                if let Some((previous, _)) = &slots[index] {
+                    return Err(Error::DuplicateConnection {
+                        port: port.1,
+                        previous: previous.clone(),
+                        output,
+                    });
+                }
+                index
+            }
+        };
+
+        match slots.get_mut(index) {
+            Some(slot) => *slot = Some((span, connection)),
+            None => return Err(too_many(count)),
+        };
+    }
+
+    let missing: Vec<String> = ports
+        .iter()
+        .zip(slots.iter())
+        .filter(|(_, slot)| slot.is_none())
+        .map(|(p, _)| p.name.clone())
+        .collect();
+    if !missing.is_empty() {
+        return Err(Error::MissingConnection {
+            call: call.clone(),
+            entity: entity.to_string(),
+            ports: missing,
+            output,
+        });
+    }
+
+    Ok(slots.into_iter().map(|s| s.unwrap().1).collect())
+}
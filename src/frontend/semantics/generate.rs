@@ -0,0 +1,201 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{evaluate, literal, syntax, Error, Token, TokenData};
+
+/// Unrolls the Generate-Loops and keeps only the selected Branches of the Generate-Conditions,
+/// using the Values of the Parameters of the Entity.
+///
+/// The Loop-Variables and Parameters are replaced by their Values in all the Indices and
+/// Parameters of the remaining Statements. Variables that are assigned with a Bit-Index, like
+/// `(carry[i + 1]) = ...`, become separate Variables with indexed Names like `carry[3]`, so
+/// that a Loop can build a Chain of Variables
+pub fn elaborate(
+    statements: Vec<syntax::BehaviourStatement>,
+    params: &HashMap<String, u32>,
+) -> Result<Vec<syntax::BehaviourStatement>, Error> {
+    let mut result = Vec::new();
+    unroll(statements, params, &mut result)?;
+
+    rename_indexed(&mut result);
+
+    Ok(result)
+}
+
+fn number(raw: &syntax::Number, values: &HashMap<String, u32>) -> Result<u32, Error> {
+    evaluate(raw, values).ok_or_else(|| Error::InvalidNumber {
+        number: raw.span.clone(),
+    })
+}
+
+/// Adds the elaborated Statements to the Result, where the Values contain the Parameters and the
+/// Variables of the surrounding Loops
+fn unroll(
+    statements: Vec<syntax::BehaviourStatement>,
+    values: &HashMap<String, u32>,
+    result: &mut Vec<syntax::BehaviourStatement>,
+) -> Result<(), Error> {
+    for stmnt in statements {
+        match stmnt {
+            syntax::BehaviourStatement::For {
+                var,
+                start,
+                end,
+                body,
+            } => {
+                let start = number(&start, values)?;
+                let end = number(&end, values)?;
+
+                for value in start..end {
+                    let mut inner = values.clone();
+                    inner.insert(literal(&var).to_string(), value);
+
+                    unroll(body.clone(), &inner, result)?;
+                }
+            }
+            syntax::BehaviourStatement::If {
+                condition,
+                body,
+                else_body,
+            } => {
+                let left = number(&condition.left, values)?;
+                let right = number(&condition.right, values)?;
+
+                let holds = match condition.op.0 {
+                    TokenData::Less => left < right,
+                    TokenData::Greater => left > right,
+                    _ => left == right,
+                };
+                let branch = if holds { body } else { else_body };
+
+                unroll(branch, values, result)?;
+            }
+            mut other => {
+                visit_statement(
+                    &mut other,
+                    &mut |operand| match &mut operand.index {
+                        Some(syntax::Index::Bit(index)) => substitute_number(index, values),
+                        Some(syntax::Index::Range { high, low }) => {
+                            substitute_number(high, values);
+                            substitute_number(low, values);
+                        }
+                        None => {}
+                    },
+                    &mut |parameter| substitute_number(parameter, values),
+                );
+
+                result.push(other);
+            }
+        };
+    }
+
+    Ok(())
+}
+
+/// Replaces the Literal by its Value, if it is known
+fn substitute(token: &mut Token, values: &HashMap<String, u32>) {
+    if let Some(value) = values.get(literal(token)) {
+        token.0 = TokenData::Literal(value.to_string());
+    }
+}
+
+fn substitute_number(raw: &mut syntax::Number, values: &HashMap<String, u32>) {
+    substitute(&mut raw.first, values);
+    for (_, term) in raw.rest.iter_mut() {
+        substitute(term, values);
+    }
+}
+
+/// Replaces every Bit of a Variable that is assigned with a Bit-Index by a Variable of its own
+fn rename_indexed(statements: &mut [syntax::BehaviourStatement]) {
+    let indexed: HashSet<String> = statements
+        .iter()
+        .filter_map(|stmnt| match stmnt {
+            syntax::BehaviourStatement::VarAssign { targets, .. } => Some(targets),
+            _ => None,
+        })
+        .flatten()
+        .filter(|t| matches!(t.operand.index, Some(syntax::Index::Bit(_))))
+        .map(|t| literal(&t.operand.name).to_string())
+        .collect();
+    if indexed.is_empty() {
+        return;
+    }
+
+    // All the Values are already substituted, so only plain Numbers are left
+    let no_values = HashMap::new();
+    for stmnt in statements.iter_mut() {
+        visit_statement(
+            stmnt,
+            &mut |operand| {
+                let index = match &operand.index {
+                    Some(syntax::Index::Bit(index)) => evaluate(index, &no_values),
+                    _ => None,
+                };
+                let base = literal(&operand.name);
+
+                if let Some(index) = index.filter(|_| indexed.contains(base)) {
+                    let name = format!("{}[{}]", base, index);
+                    operand.name = Token(TokenData::Literal(name), operand.span.clone());
+                    operand.index = None;
+                }
+            },
+            &mut |_| {},
+        );
+    }
+}
+
+/// Calls the Functions for all the Operands in the Statement and for the Parameters of all the
+/// Calls in it
+fn visit_statement(
+    stmnt: &mut syntax::BehaviourStatement,
+    operand: &mut dyn FnMut(&mut syntax::Operand),
+    parameter: &mut dyn FnMut(&mut syntax::Number),
+) {
+    match stmnt {
+        syntax::BehaviourStatement::PortAssign { targets, value, .. }
+        | syntax::BehaviourStatement::VarAssign { targets, value, .. } => {
+            targets.iter_mut().for_each(|t| operand(&mut t.operand));
+            value
+                .values
+                .iter_mut()
+                .for_each(|v| visit_expression(v, operand, parameter));
+        }
+        syntax::BehaviourStatement::Table {
+            inputs, targets, ..
+        } => {
+            inputs
+                .iter_mut()
+                .for_each(|i| visit_expression(i, operand, parameter));
+            targets.iter_mut().for_each(&mut *operand);
+        }
+        syntax::BehaviourStatement::For { .. } | syntax::BehaviourStatement::If { .. } => {
+            unreachable!("Generate-Blocks are unrolled before visiting their Statements")
+        }
+    };
+}
+
+fn visit_expression(
+    expression: &mut syntax::Expression,
+    operand: &mut dyn FnMut(&mut syntax::Operand),
+    parameter: &mut dyn FnMut(&mut syntax::Number),
+) {
+    match expression {
+        syntax::Expression::Operand(o) => operand(o),
+        syntax::Expression::Text(_) => {}
+        syntax::Expression::Call {
+            parameters,
+            arguments,
+            ..
+        } => {
+            parameters.iter_mut().for_each(&mut *parameter);
+            arguments
+                .iter_mut()
+                .for_each(|a| visit_expression(&mut a.value, operand, parameter));
+        }
+        syntax::Expression::Not { inner, .. } => visit_expression(inner, operand, parameter),
+        syntax::Expression::Binary { left, right, .. } => {
+            visit_expression(left, operand, parameter);
+            visit_expression(right, operand, parameter);
+        }
+    };
+}
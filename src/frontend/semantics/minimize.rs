@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+
+/// A Product-Term of a Function with up to 32 Inputs, which is on for every Combination of
+/// Inputs that matches the `value` in all the Bits that are not part of `dont_care`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Implicant {
+    pub value: u32,
+    pub dont_care: u32,
+}
+
+impl Implicant {
+    fn covers(&self, minterm: u32) -> bool {
+        minterm & !self.dont_care == self.value
+    }
+}
+
+/// Finds all the Prime-Implicants of the Function, by repeatedly merging Implicants that only
+/// differ in a single Bit (Quine-McCluskey)
+fn prime_implicants(width: u32, minterms: impl Iterator<Item = u32>) -> Vec<Implicant> {
+    let mut current: HashSet<Implicant> = minterms
+        .map(|value| Implicant {
+            value,
+            dont_care: 0,
+        })
+        .collect();
+    let mut primes = Vec::new();
+
+    while !current.is_empty() {
+        let mut next = HashSet::new();
+        let mut merged = HashSet::new();
+
+        for implicant in current.iter() {
+            for bit in (0..width).map(|b| 1 << b) {
+                if implicant.dont_care & bit != 0 {
+                    continue;
+                }
+
+                let other = Implicant {
+                    value: implicant.value ^ bit,
+                    dont_care: implicant.dont_care,
+                };
+                if current.contains(&other) {
+                    next.insert(Implicant {
+                        value: implicant.value & !bit,
+                        dont_care: implicant.dont_care | bit,
+                    });
+                    merged.insert(*implicant);
+                }
+            }
+        }
+
+        primes.extend(current.into_iter().filter(|i| !merged.contains(i)));
+        current = next;
+    }
+
+    // Sorted, so that the chosen Cover does not depend on the Iteration-Order of the Sets
+    primes.sort();
+    primes
+}
+
+/// Returns a minimal Sum-of-Products for the Function with the given Number of Inputs, which is
+/// on for all the `on` Minterms and may have any Value for the `dont_care` Minterms.
+///
+/// All the essential Prime-Implicants are used and the remaining Minterms are covered greedily,
+/// preferring the Implicants that cover the most Minterms with the fewest Inputs
+pub fn minimize(width: u32, on: &[u32], dont_care: &[u32]) -> Vec<Implicant> {
+    let primes = prime_implicants(width, on.iter().chain(dont_care.iter()).copied());
+
+    let mut cover: Vec<Implicant> = Vec::new();
+    for minterm in on {
+        let mut covering = primes.iter().filter(|p| p.covers(*minterm));
+        if let (Some(essential), None) = (covering.next(), covering.next()) {
+            if !cover.contains(essential) {
+                cover.push(*essential);
+            }
+        }
+    }
+
+    let mut remaining: Vec<u32> = on
+        .iter()
+        .copied()
+        .filter(|m| !cover.iter().any(|i| i.covers(*m)))
+        .collect();
+    while !remaining.is_empty() {
+        let best = *primes
+            .iter()
+            .max_by_key(|p| {
+                let covered = remaining.iter().filter(|m| p.covers(**m)).count();
+                (covered, p.dont_care.count_ones())
+            })
+            .unwrap();
+
+        remaining.retain(|m| !best.covers(*m));
+        cover.push(best);
+    }
+
+    cover.sort();
+    cover
+}
@@ -0,0 +1,132 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    path::{Path, PathBuf},
+};
+
+use super::{literal, syntax, Token, TokenData};
+
+/// The widest Words a ROM can store
+pub const MAX_DATA_WIDTH: u32 = 64;
+
+/// The Contents of a ROM-File
+#[derive(Debug, Clone)]
+pub struct RomData {
+    pub words: Vec<u64>,
+    pub data_width: u32,
+}
+
+/// The Contents of the ROM-Files used by an Entity by their Paths, or the Reason why they couldn't
+/// be loaded, so every File is only read once
+pub type Files = HashMap<PathBuf, Result<RomData, String>>;
+
+/// The Path of the File used by the ROM, which is relative to the Source the Call is in
+pub fn path(call: &Token, file: &str) -> PathBuf {
+    Path::new(call.1.name())
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(file)
+}
+
+/// Loads the Files of all the ROMs in the Statements
+pub fn load_files(statements: &[syntax::BehaviourStatement]) -> Files {
+    let mut files = Files::new();
+    for stmnt in statements {
+        match stmnt {
+            syntax::BehaviourStatement::PortAssign { value, .. }
+            | syntax::BehaviourStatement::VarAssign { value, .. } => value
+                .values
+                .iter()
+                .for_each(|v| collect_files(v, &mut files)),
+            syntax::BehaviourStatement::Table { inputs, .. } => {
+                inputs.iter().for_each(|i| collect_files(i, &mut files))
+            }
+            syntax::BehaviourStatement::For { .. } | syntax::BehaviourStatement::If { .. } => {
+                unreachable!("Generate-Blocks are already elaborated")
+            }
+        };
+    }
+
+    files
+}
+
+fn collect_files(expression: &syntax::Expression, files: &mut Files) {
+    match expression {
+        syntax::Expression::Call {
+            name, arguments, ..
+        } => {
+            if let (
+                "rom",
+                Some(syntax::Argument {
+                    value: syntax::Expression::Text(Token(TokenData::Text(file), _)),
+                    ..
+                }),
+            ) = (literal(name), arguments.first())
+            {
+                let path = path(name, file);
+                if let Entry::Vacant(entry) = files.entry(path) {
+                    let data = load(entry.key());
+                    entry.insert(data);
+                }
+            }
+
+            arguments
+                .iter()
+                .for_each(|a| collect_files(&a.value, files));
+        }
+        syntax::Expression::Not { inner, .. } => collect_files(inner, files),
+        syntax::Expression::Binary { left, right, .. } => {
+            collect_files(left, files);
+            collect_files(right, files);
+        }
+        syntax::Expression::Operand(_) | syntax::Expression::Text(_) => {}
+    }
+}
+
+/// Loads the Words from the File, depending on its Extension:
+///
+/// * `.bin` Files are raw Bytes, where every Byte is a Word of 8 Bits
+/// * `.hex` Files contain hexadecimal Words separated by Whitespace, where `//` starts a Comment
+///   up to the End of the Line. The Words are as wide as the longest Word in the File
+pub fn load(path: &Path) -> Result<RomData, String> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("bin") => {
+            let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+
+            Ok(RomData {
+                words: bytes.into_iter().map(u64::from).collect(),
+                data_width: 8,
+            })
+        }
+        Some("hex") => {
+            let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+            parse_hex(&content)
+        }
+        _ => Err("expected a `.hex` or `.bin` file".to_string()),
+    }
+}
+
+fn parse_hex(content: &str) -> Result<RomData, String> {
+    let mut words = Vec::new();
+    let mut digits = 1;
+
+    let without_comments = content
+        .lines()
+        .map(|line| line.split("//").next().unwrap_or_default());
+    for word in without_comments.flat_map(str::split_whitespace) {
+        let value = u64::from_str_radix(word, 16)
+            .map_err(|_| format!("`{}` is not a hexadecimal word", word))?;
+
+        words.push(value);
+        digits = digits.max(word.len() as u32);
+    }
+
+    let data_width = digits * 4;
+    if data_width > MAX_DATA_WIDTH {
+        return Err(format!(
+            "the words have {} bits, but at most {} are supported",
+            data_width, MAX_DATA_WIDTH
+        ));
+    }
+
+    Ok(RomData { words, data_width })
+}
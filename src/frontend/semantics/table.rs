@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use crate::graph;
+
+use super::{
+    minimize::{self, Implicant},
+    syntax, Error, GraphBuilder, Span, Token, TokenData,
+};
+
+/// The largest Number of Input-Bits of a Truth-Table, as all their Combinations are considered
+/// when minimizing it
+pub const MAX_INPUTS: u32 = 12;
+
+/// Splits the Cells of one Side of a Row into the Values of the single Bits, where `None` is a
+/// Don't-Care, and checks that there is a Value for every Bit
+fn parse_cells(cells: &[Token], width: u32, row: &Span) -> Result<Vec<Option<bool>>, Error> {
+    let mut values = Vec::new();
+    for cell in cells {
+        match &cell.0 {
+            TokenData::Literal(text) => {
+                for (offset, c) in text.char_indices() {
+                    values.push(match c {
+                        '0' => Some(false),
+                        '1' => Some(true),
+                        _ => {
+                            let start = cell.1.start() + offset;
+                            return Err(Error::InvalidTableCell {
+                                cell: cell.1.sub_span(start..start + c.len_utf8()),
+                            });
+                        }
+                    });
+                }
+            }
+            _ => values.push(None),
+        };
+    }
+
+    if values.len() != width as usize {
+        let cells = match (cells.first(), cells.last()) {
+            (Some(first), Some(last)) => first.1.join(&last.1),
+            _ => row.clone(),
+        };
+        return Err(Error::TableRowWidth {
+            cells,
+            expected: width,
+            got: values.len() as u32,
+        });
+    }
+
+    Ok(values)
+}
+
+/// Computes the minimized Sum-of-Products for every Output-Bit of the Table. Inputs that match
+/// none of the Rows turn off all the Outputs, while a `-` in the Outputs of a Row leaves the
+/// Value up to the Minimization
+pub fn parse_rows(
+    rows: &[syntax::TableRow],
+    input_width: u32,
+    output_width: u32,
+) -> Result<Vec<Vec<Implicant>>, Error> {
+    // The Outputs for every Combination of Inputs that matches a Row, together with the first
+    // Row that matched it
+    let mut entries: HashMap<u32, (Vec<Option<bool>>, &Span)> = HashMap::new();
+
+    for row in rows {
+        let inputs = parse_cells(&row.inputs, input_width, &row.span)?;
+        let outputs = parse_cells(&row.outputs, output_width, &row.span)?;
+
+        let value = inputs
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| **v == Some(true))
+            .fold(0, |acc, (i, _)| acc | 1 << i);
+        let free: Vec<_> = (0..input_width)
+            .filter(|i| inputs[*i as usize].is_none())
+            .collect();
+
+        for combination in 0..1u32 << free.len() {
+            let minterm = free
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| combination & 1 << i != 0)
+                .fold(value, |acc, (_, bit)| acc | 1 << bit);
+
+            match entries.get_mut(&minterm) {
+                Some((existing, previous)) => {
+                    for (old, new) in existing.iter_mut().zip(outputs.iter()) {
+                        match (*old, *new) {
+                            (Some(a), Some(b)) if a != b => {
+                                return Err(Error::ConflictingRows {
+                                    row: row.span.clone(),
+                                    previous: (*previous).clone(),
+                                });
+                            }
+                            (None, new) => *old = new,
+                            _ => {}
+                        };
+                    }
+                }
+                None => {
+                    entries.insert(minterm, (outputs.clone(), &row.span));
+                }
+            };
+        }
+    }
+
+    let mut minterms: Vec<_> = entries.keys().copied().collect();
+    minterms.sort_unstable();
+
+    Ok((0..output_width as usize)
+        .map(|bit| {
+            let on: Vec<_> = minterms
+                .iter()
+                .copied()
+                .filter(|m| entries[m].0[bit] == Some(true))
+                .collect();
+            let dont_care: Vec<_> = minterms
+                .iter()
+                .copied()
+                .filter(|m| entries[m].0[bit].is_none())
+                .collect();
+
+            minimize::minimize(input_width, &on, &dont_care)
+        })
+        .collect())
+}
+
+impl GraphBuilder {
+    /// Adds the Sum-of-Products for every Output-Bit of a Table, where the Columns are the Sources
+    /// of the Input-Bits in the Order of the Bits of the Implicants
+    pub fn table(&mut self, columns: &[(u32, u32)], terms: &[Vec<Implicant>]) -> Vec<(u32, u32)> {
+        let mut inverted: HashMap<usize, (u32, u32)> = HashMap::new();
+        let mut products: HashMap<Implicant, (u32, u32)> = HashMap::new();
+
+        terms
+            .iter()
+            .map(|implicants| {
+                let mut sum = Vec::new();
+                for implicant in implicants {
+                    if let Some(product) = products.get(implicant) {
+                        sum.push(*product);
+                        continue;
+                    }
+
+                    let literals: Vec<_> = (0..columns.len())
+                        .filter(|c| implicant.dont_care & 1 << c == 0)
+                        .map(|c| {
+                            if implicant.value & 1 << c != 0 {
+                                columns[c]
+                            } else {
+                                *inverted.entry(c).or_insert_with(|| {
+                                    self.gate(graph::entity::BuiltinOp::Not, &[columns[c]])
+                                })
+                            }
+                        })
+                        .collect();
+
+                    // An Implicant without any Literals covers all the Inputs
+                    let product = if literals.is_empty() {
+                        let node = graph::entity::NodeType::Constant { value: true };
+                        (self.add_node(node), 0)
+                    } else {
+                        self.reduce(graph::entity::BuiltinOp::And, literals)
+                    };
+                    products.insert(*implicant, product);
+                    sum.push(product);
+                }
+
+                if sum.is_empty() {
+                    let node = graph::entity::NodeType::Constant { value: false };
+                    (self.add_node(node), 0)
+                } else {
+                    self.reduce(graph::entity::BuiltinOp::Or, sum)
+                }
+            })
+            .collect()
+    }
+}
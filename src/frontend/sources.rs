@@ -0,0 +1,69 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    path::{Path, PathBuf},
+};
+
+use super::{syntax, tokens, ParseError, Span};
+
+/// Returns the Path of a File used by the given Source, which is relative to the Directory of
+/// the Source
+fn resolve(source: &Span, path: &str) -> PathBuf {
+    Path::new(source.name())
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(path)
+}
+
+/// Parses the Syntax of the Source and of every File it uses, directly or through other Files,
+/// returning the Entities of all of them, starting with the ones of the given Source.
+///
+/// Every File is only loaded once, even if it is used multiple Times or the Files use each other.
+/// All the Spans keep the Name of the File they came from, so Diagnostics point into that File
+pub fn load(main: Span) -> Result<Vec<syntax::Entity>, ParseError> {
+    let mut loaded: HashSet<PathBuf> = HashSet::new();
+    loaded.insert(canonical(Path::new(main.name())));
+
+    let mut pending = VecDeque::from([main]);
+    let mut entities = Vec::new();
+    let mut errors = Vec::new();
+
+    while let Some(source) = pending.pop_front() {
+        let tokens = tokens::tokenize(source.clone()).map_err(ParseError::Tokens)?;
+
+        let (file, syntax_errors) = syntax::parse(tokens);
+        errors.extend(syntax_errors);
+
+        for used in file.uses {
+            let text = match &used.path.0 {
+                tokens::TokenData::Text(text) => text,
+                _ => unreachable!("Uses only contain Text-Tokens"),
+            };
+
+            let path = resolve(&source, text);
+            if !loaded.insert(canonical(&path)) {
+                continue;
+            }
+
+            let content =
+                std::fs::read_to_string(&path).map_err(|e| ParseError::UnreadableFile {
+                    file: used.path.1.clone(),
+                    reason: e.to_string(),
+                })?;
+            pending.push_back(Span::with_name(path.display().to_string(), content));
+        }
+
+        entities.extend(file.entities);
+    }
+
+    if !errors.is_empty() {
+        return Err(ParseError::Syntax(errors));
+    }
+
+    Ok(entities)
+}
+
+/// The canonical Form of the Path, so different Paths to the same File are detected, or the Path
+/// itself if the File doesn't exist
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
@@ -1,290 +1,1390 @@
+use std::{fmt::Display, iter::Peekable};
+
 use super::tokens::{Token, TokenData};
-use super::Span;
+use super::{Diagnostic, Span};
 
 mod scopeiter;
 use scopeiter::*;
 
 #[derive(Debug)]
 pub enum Error {
-    MissingEntityName,
     UnexpectedToken {
         expected: Vec<TokenNames>,
         got: Token,
     },
+    UnexpectedEnd {
+        expected: Vec<TokenNames>,
+        end: Span,
+    },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TokenNames {
+    Entity,
     Literal,
     InPorts,
     OutPorts,
     Behaviour,
     OpenCurly,
+    OpenParen,
+    CloseParen,
+    OpenBracket,
+    CloseBracket,
+    Comma,
+    Colon,
+    Semicolon,
+    Assign,
+    PortAssign,
+    Bang,
+    Table,
+    Minus,
+    Arrow,
+    FatArrow,
+    Use,
+    Text,
+    Less,
+    Greater,
+    For,
+    In,
+    If,
+    DotDot,
+    Equals,
+}
+
+impl TokenNames {
+    fn matches(&self, data: &TokenData) -> bool {
+        matches!(
+            (self, data),
+            (Self::Entity, TokenData::Entity)
+                | (Self::Literal, TokenData::Literal(_))
+                | (Self::InPorts, TokenData::InPorts)
+                | (Self::OutPorts, TokenData::OutPorts)
+                | (Self::Behaviour, TokenData::Behaviour)
+                | (Self::OpenCurly, TokenData::OpenCurly)
+                | (Self::OpenParen, TokenData::OpenParen)
+                | (Self::CloseParen, TokenData::CloseParen)
+                | (Self::OpenBracket, TokenData::OpenBracket)
+                | (Self::CloseBracket, TokenData::CloseBracket)
+                | (Self::Comma, TokenData::Comma)
+                | (Self::Colon, TokenData::Colon)
+                | (Self::Semicolon, TokenData::Semicolon)
+                | (Self::Assign, TokenData::Assign)
+                | (Self::PortAssign, TokenData::PortAssign)
+                | (Self::Bang, TokenData::Bang)
+                | (Self::Table, TokenData::Table)
+                | (Self::Minus, TokenData::Minus)
+                | (Self::Arrow, TokenData::Arrow)
+                | (Self::FatArrow, TokenData::FatArrow)
+                | (Self::Use, TokenData::Use)
+                | (Self::Text, TokenData::Text(_))
+                | (Self::Less, TokenData::Less)
+                | (Self::Greater, TokenData::Greater)
+                | (Self::For, TokenData::For)
+                | (Self::In, TokenData::In)
+                | (Self::If, TokenData::If)
+                | (Self::DotDot, TokenData::DotDot)
+                | (Self::Equals, TokenData::Equals)
+        )
+    }
+}
+
+impl Display for TokenNames {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Entity => write!(f, "`entity`"),
+            Self::Literal => write!(f, "a name"),
+            Self::InPorts => write!(f, "`in_ports`"),
+            Self::OutPorts => write!(f, "`out_ports`"),
+            Self::Behaviour => write!(f, "`behaviour`"),
+            Self::OpenCurly => write!(f, "`{{`"),
+            Self::OpenParen => write!(f, "`(`"),
+            Self::CloseParen => write!(f, "`)`"),
+            Self::OpenBracket => write!(f, "`[`"),
+            Self::CloseBracket => write!(f, "`]`"),
+            Self::Comma => write!(f, "`,`"),
+            Self::Colon => write!(f, "`:`"),
+            Self::Semicolon => write!(f, "`;`"),
+            Self::Assign => write!(f, "`=`"),
+            Self::PortAssign => write!(f, "`<=`"),
+            Self::Bang => write!(f, "`!`"),
+            Self::Table => write!(f, "`table`"),
+            Self::Minus => write!(f, "`-`"),
+            Self::Arrow => write!(f, "`->`"),
+            Self::FatArrow => write!(f, "`=>`"),
+            Self::Use => write!(f, "`use`"),
+            Self::Text => write!(f, "a file name in quotes"),
+            Self::Less => write!(f, "`<`"),
+            Self::Greater => write!(f, "`>`"),
+            Self::For => write!(f, "`for`"),
+            Self::In => write!(f, "`in`"),
+            Self::If => write!(f, "`if`"),
+            Self::DotDot => write!(f, "`..`"),
+            Self::Equals => write!(f, "`==`"),
+        }
+    }
+}
+
+fn expected_str(expected: &[TokenNames]) -> String {
+    match expected {
+        [] => "nothing".to_string(),
+        [single] => single.to_string(),
+        [rest @ .., last] => {
+            let rest: Vec<_> = rest.iter().map(|n| n.to_string()).collect();
+            format!("one of {} or {}", rest.join(", "), last)
+        }
+    }
+}
+
+impl Error {
+    pub fn diagnostic(&self) -> Diagnostic {
+        match self {
+            Self::UnexpectedToken { expected, got } => Diagnostic::new(
+                got.1.clone(),
+                format!(
+                    "expected {}, found `{}`",
+                    expected_str(expected),
+                    got.1.content()
+                ),
+            ),
+            Self::UnexpectedEnd { expected, end } => {
+                let found = if end.content() == "}" {
+                    "the end of the block"
+                } else {
+                    "the end of the file"
+                };
+
+                Diagnostic::new(
+                    end.clone(),
+                    format!("expected {}, found {}", expected_str(expected), found),
+                )
+            }
+        }
+    }
+}
+
+/// Returns the next Token in the Scope or an Error, if the Scope has already ended
+fn next_token<I>(tokens: &mut ScopeIter<I>, expected: &[TokenNames]) -> Result<Token, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    match tokens.next() {
+        Some(t) => Ok(t),
+        None => Err(Error::UnexpectedEnd {
+            expected: expected.to_vec(),
+            end: tokens.last_span().clone(),
+        }),
+    }
+}
+
+/// Returns the next Token in the Scope, if it is of the expected Kind
+fn expect_token<I>(tokens: &mut ScopeIter<I>, expected: TokenNames) -> Result<Token, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    let tok = next_token(tokens, &[expected])?;
+    if expected.matches(&tok.0) {
+        Ok(tok)
+    } else {
+        Err(Error::UnexpectedToken {
+            expected: vec![expected],
+            got: tok,
+        })
+    }
+}
+
+/// A reference to a Port or Variable, optionally selecting only some of its Bits
+#[derive(Debug, Clone)]
+pub struct Operand {
+    pub name: Token,
+    pub index: Option<Index>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum Index {
+    /// `a[3]`
+    Bit(Number),
+    /// `a[7:4]`
+    Range { high: Number, low: Number },
+}
+
+/// A Number that is known once the Entity is elaborated, like `3`, `N` or `i + 1`
+#[derive(Debug, Clone)]
+pub struct Number {
+    pub first: Token,
+    /// The following Terms, together with the `+` or `-` in front of them
+    pub rest: Vec<(Token, Token)>,
+    pub span: Span,
+}
+
+/// Parses a Number, which is a Sum of Literals starting with the given Token
+fn parse_number<I>(first: Token, tokens: &mut ScopeIter<I>) -> Result<Number, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    if !TokenNames::Literal.matches(&first.0) {
+        return Err(Error::UnexpectedToken {
+            expected: vec![TokenNames::Literal],
+            got: first,
+        });
+    }
+
+    let mut span = first.1.clone();
+    let mut rest = Vec::new();
+    while matches!(
+        tokens.peek(),
+        Some(Token(TokenData::Plus | TokenData::Minus, _))
+    ) {
+        let op = tokens.next().unwrap();
+        let term = expect_token(tokens, TokenNames::Literal)?;
+        span = span.join(&term.1);
+        rest.push((op, term));
+    }
+
+    Ok(Number { first, rest, span })
 }
 
-fn parse_args(tokens: &mut dyn Iterator<Item = Token>) -> Vec<Token> {
+/// Parses the Index after an opening Bracket, returning it together with the closing Bracket
+fn parse_index<I>(tokens: &mut ScopeIter<I>) -> Result<(Index, Token), Error>
+where
+    I: Iterator<Item = Token>,
+{
+    let first = next_token(tokens, &[TokenNames::Literal])?;
+    let first = parse_number(first, tokens)?;
+
+    let next_tok = next_token(tokens, &[TokenNames::Colon, TokenNames::CloseBracket])?;
+    match next_tok.0 {
+        TokenData::CloseBracket => Ok((Index::Bit(first), next_tok)),
+        TokenData::Colon => {
+            let low = next_token(tokens, &[TokenNames::Literal])?;
+            let low = parse_number(low, tokens)?;
+            let close = expect_token(tokens, TokenNames::CloseBracket)?;
+
+            Ok((Index::Range { high: first, low }, close))
+        }
+        _ => Err(Error::UnexpectedToken {
+            expected: vec![TokenNames::Colon, TokenNames::CloseBracket],
+            got: next_tok,
+        }),
+    }
+}
+
+/// Parses an Operand starting with the given Name, including its optional Index
+fn parse_operand<I>(name: Token, tokens: &mut ScopeIter<I>) -> Result<Operand, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    match &name.0 {
+        TokenData::Literal(_) => {}
+        _ => {
+            return Err(Error::UnexpectedToken {
+                expected: vec![TokenNames::Literal],
+                got: name,
+            })
+        }
+    };
+
+    if !matches!(tokens.peek(), Some(Token(TokenData::OpenBracket, _))) {
+        return Ok(Operand {
+            span: name.1.clone(),
+            name,
+            index: None,
+        });
+    }
+    tokens.next();
+
+    let (index, close) = parse_index(tokens)?;
+    Ok(Operand {
+        span: name.1.join(&close.1),
+        name,
+        index: Some(index),
+    })
+}
+
+/// Parses a comma separated List of Elements up to and including the closing Parenthesis
+fn parse_list<I, T, F>(tokens: &mut ScopeIter<I>, mut element: F) -> Result<Vec<T>, Error>
+where
+    I: Iterator<Item = Token>,
+    F: FnMut(Token, &mut ScopeIter<I>) -> Result<T, Error>,
+{
     let mut result = Vec::new();
 
-    while let Some(tok) = tokens.next() {
-        match &tok.0 {
+    if matches!(tokens.peek(), Some(Token(TokenData::CloseParen, _))) {
+        tokens.next();
+        return Ok(result);
+    }
+
+    loop {
+        let tok = next_token(tokens, &[TokenNames::Literal, TokenNames::CloseParen])?;
+        result.push(element(tok, tokens)?);
+
+        let next_tok = next_token(tokens, &[TokenNames::Comma, TokenNames::CloseParen])?;
+        match next_tok.0 {
+            TokenData::Comma => {}
             TokenData::CloseParen => break,
-            TokenData::Literal(_) => {
-                result.push(tok);
-
-                let next_tok = tokens.next().unwrap();
-                match next_tok.0 {
-                    TokenData::Comma => {}
-                    TokenData::CloseParen => break,
-                    other => panic!("Unexpected Token: {:?}", other),
-                };
+            _ => {
+                return Err(Error::UnexpectedToken {
+                    expected: vec![TokenNames::Comma, TokenNames::CloseParen],
+                    got: next_tok,
+                })
             }
-            other => panic!("Unexpected arg: {:?}", other),
         };
     }
 
-    result
+    Ok(result)
+}
+
+/// A Target of an Assignment, which may be bound to a named Output of the called Entity, like
+/// `.carry_out: c`
+#[derive(Debug, Clone)]
+pub struct Target {
+    pub port: Option<Token>,
+    pub operand: Operand,
+}
+
+fn parse_target<I>(tok: Token, tokens: &mut ScopeIter<I>) -> Result<Target, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    if !matches!(tok.0, TokenData::Dot) {
+        return Ok(Target {
+            port: None,
+            operand: parse_operand(tok, tokens)?,
+        });
+    }
+
+    let port = expect_token(tokens, TokenNames::Literal)?;
+    expect_token(tokens, TokenNames::Colon)?;
+    let name = next_token(tokens, &[TokenNames::Literal])?;
+
+    Ok(Target {
+        port: Some(port),
+        operand: parse_operand(name, tokens)?,
+    })
+}
+
+fn parse_targets<I>(tokens: &mut ScopeIter<I>) -> Result<Vec<Target>, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    parse_list(tokens, parse_target)
+}
+
+/// A `use "file.mcl";` Statement, which makes the Entities of the File available
+#[derive(Debug)]
+pub struct Use {
+    /// The Text-Token containing the Path of the File
+    pub path: Token,
 }
 
+/// The Contents of a single File
 #[derive(Debug)]
+pub struct File {
+    pub uses: Vec<Use>,
+    pub entities: Vec<Entity>,
+}
+
+#[derive(Debug, Clone)]
 pub struct Entity {
     pub name: Span,
-    pub in_ports: Vec<(Token, Token)>,
-    pub out_ports: Vec<(Token, Token)>,
+    /// The compile-time Parameters of a generic Entity like `entity Adder<N: int>`
+    pub params: Vec<Param>,
+    pub doc: Option<String>,
+    pub in_ports: Vec<Port>,
+    pub out_ports: Vec<Port>,
     pub behaviour: Vec<BehaviourStatement>,
 }
 
-fn parse_ports<I>(mut tokens: ScopeIter<I>) -> Vec<(Token, Token)>
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub name: Token,
+    pub ty: Token,
+}
+
+#[derive(Debug, Clone)]
+pub struct Port {
+    pub name: Token,
+    pub ty: Type,
+    pub doc: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Type {
+    pub name: Token,
+    /// The Width of a `bits[N]` Type
+    pub width: Option<Number>,
+    pub span: Span,
+}
+
+/// Skips all the Tokens up to and including the next Semicolon in the Scope, so that parsing can
+/// continue with the next Statement after an Error
+fn synchronize<I>(tokens: &mut ScopeIter<I>, error: &Error)
 where
     I: Iterator<Item = Token>,
 {
-    let mut result = Vec::new();
+    let at_boundary = match error {
+        Error::UnexpectedToken { got, .. } => matches!(got.0, TokenData::Semicolon),
+        Error::UnexpectedEnd { .. } => true,
+    };
+    if at_boundary {
+        return;
+    }
 
-    while let Some(tok) = tokens.next() {
-        match &tok.0 {
-            TokenData::Literal(_) => {
-                let name_tok = tok;
-
-                let colon_tok = tokens.next().unwrap();
-                match colon_tok.0 {
-                    TokenData::Colon => {}
-                    other => panic!("Expected :, got {:?}", other),
-                };
+    for tok in tokens.by_ref() {
+        if let TokenData::Semicolon = tok.0 {
+            break;
+        }
+    }
+}
 
-                let ty_tok = tokens.next().unwrap();
+/// Combines the Lines of consecutive Doc-Comments into a single Documentation String
+fn doc_string(lines: Vec<String>) -> Option<String> {
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
 
-                let semicolon_tok = tokens.next().unwrap();
-                match semicolon_tok.0 {
-                    TokenData::Semicolon => {}
-                    other => panic!("Expected ;, got {:?}", other),
-                };
+/// Parses a Type, like `bit` or `bits[8]`
+fn parse_type<I>(tokens: &mut ScopeIter<I>) -> Result<Type, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    let name = expect_token(tokens, TokenNames::Literal)?;
 
-                result.push((name_tok, ty_tok));
+    if name.1.content() != "bits" {
+        return Ok(Type {
+            span: name.1.clone(),
+            name,
+            width: None,
+        });
+    }
+
+    expect_token(tokens, TokenNames::OpenBracket)?;
+    let first = next_token(tokens, &[TokenNames::Literal])?;
+    let width = parse_number(first, tokens)?;
+    let close = expect_token(tokens, TokenNames::CloseBracket)?;
+
+    Ok(Type {
+        span: name.1.join(&close.1),
+        name,
+        width: Some(width),
+    })
+}
+
+fn parse_port<I>(
+    name_tok: Token,
+    doc: Option<String>,
+    tokens: &mut ScopeIter<I>,
+) -> Result<Port, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    match &name_tok.0 {
+        TokenData::Literal(_) => {}
+        _ => {
+            return Err(Error::UnexpectedToken {
+                expected: vec![TokenNames::Literal],
+                got: name_tok,
+            })
+        }
+    };
+
+    expect_token(tokens, TokenNames::Colon)?;
+    let ty = parse_type(tokens)?;
+    expect_token(tokens, TokenNames::Semicolon)?;
+
+    Ok(Port {
+        name: name_tok,
+        ty,
+        doc,
+    })
+}
+
+fn parse_ports<I>(mut tokens: ScopeIter<I>, errors: &mut Vec<Error>) -> Vec<Port>
+where
+    I: Iterator<Item = Token>,
+{
+    let mut result = Vec::new();
+    let mut doc_lines = Vec::new();
+
+    while let Some(tok) = tokens.next() {
+        if let TokenData::DocComment(text) = tok.0 {
+            doc_lines.push(text);
+            continue;
+        }
+
+        let doc = doc_string(std::mem::take(&mut doc_lines));
+        match parse_port(tok, doc, &mut tokens) {
+            Ok(port) => result.push(port),
+            Err(e) => {
+                synchronize(&mut tokens, &e);
+                errors.push(e);
             }
-            other => panic!("Expected Port-Name or ;, got {:?}", other),
         };
     }
 
     result
 }
 
-#[derive(Debug)]
-pub enum BehaviourValue {
-    Operation { name: Token, arguments: Vec<Token> },
-    Variables { vars: Vec<Token> },
+/// An infix Expression, like `(a & b) | !c ^ d`
+#[derive(Debug, Clone)]
+pub enum Expression {
+    Operand(Operand),
+    /// A quoted Text, which is only used to refer to other Files
+    Text(Token),
+    /// A Call of a Builtin or another Entity, like `and(a, b)` or `Adder<4>(a, b)`
+    Call {
+        name: Token,
+        /// The Values of the Parameters of a generic Entity, which are Numbers or the Names of
+        /// Parameters of the calling Entity
+        parameters: Vec<Number>,
+        arguments: Vec<Argument>,
+        span: Span,
+    },
+    /// `!a`, where the Span also covers the `!`
+    Not {
+        inner: Box<Expression>,
+        span: Span,
+    },
+    Binary {
+        op: Token,
+        left: Box<Expression>,
+        right: Box<Expression>,
+        span: Span,
+    },
+}
+
+/// An Argument of a Call, which may be connected to a named Input of the called Entity, like
+/// `carry_in: c`
+#[derive(Debug, Clone)]
+pub struct Argument {
+    pub port: Option<Token>,
+    pub value: Expression,
+}
+
+impl Argument {
+    pub fn span(&self) -> Span {
+        match &self.port {
+            Some(port) => port.1.join(self.value.span()),
+            None => self.value.span().clone(),
+        }
+    }
+}
+
+fn parse_argument<I>(tok: Token, tokens: &mut ScopeIter<I>) -> Result<Argument, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    let named = matches!(tok.0, TokenData::Literal(_))
+        && matches!(tokens.peek(), Some(Token(TokenData::Colon, _)));
+    if !named {
+        return Ok(Argument {
+            port: None,
+            value: parse_expression(tok, tokens)?,
+        });
+    }
+
+    tokens.next();
+    let first = next_token(
+        tokens,
+        &[TokenNames::Literal, TokenNames::OpenParen, TokenNames::Bang],
+    )?;
+
+    Ok(Argument {
+        port: Some(tok),
+        value: parse_expression(first, tokens)?,
+    })
+}
+
+impl Expression {
+    pub fn span(&self) -> &Span {
+        match self {
+            Self::Operand(o) => &o.span,
+            Self::Text(t) => &t.1,
+            Self::Call { span, .. } => span,
+            Self::Not { span, .. } => span,
+            Self::Binary { span, .. } => span,
+        }
+    }
+}
+
+/// The Precedence of the Binary Operator, where a higher Precedence binds more tightly
+fn binary_precedence(data: &TokenData) -> Option<u8> {
+    match data {
+        TokenData::Pipe => Some(1),
+        TokenData::Caret => Some(2),
+        TokenData::Ampersand => Some(3),
+        TokenData::Equals => Some(4),
+        TokenData::Less => Some(5),
+        TokenData::Plus | TokenData::Minus => Some(6),
+        _ => None,
+    }
 }
 
-fn parse_value(tokens: &mut dyn Iterator<Item = Token>) -> BehaviourValue {
-    let init_token = tokens.next().unwrap();
+/// Checks if the next Tokens are the Parameters of a Call like `<4, N - 1>`, which can't be confused
+/// with a Comparison, as `>` is not an Operator
+fn starts_parameters<I>(tokens: &mut ScopeIter<I>) -> bool
+where
+    I: Iterator<Item = Token>,
+{
+    if !matches!(tokens.peek(), Some(Token(TokenData::Less, _))) {
+        return false;
+    }
+
+    // Every Parameter is a Sum of Literals, like `N - 1`
+    let mut offset = 1;
+    loop {
+        if !matches!(
+            tokens.peek_nth(offset),
+            Some(Token(TokenData::Literal(_), _))
+        ) {
+            return false;
+        }
 
-    match &init_token.0 {
-        TokenData::Literal(_) => {
-            let name_tok = init_token;
+        match tokens.peek_nth(offset + 1) {
+            Some(Token(TokenData::Comma | TokenData::Plus | TokenData::Minus, _)) => offset += 2,
+            Some(Token(TokenData::Greater, _)) => return true,
+            _ => return false,
+        }
+    }
+}
 
-            let opening_tok = tokens.next().unwrap();
-            match opening_tok.0 {
-                TokenData::OpenParen => {}
-                other => panic!("Unexpected Token: {:?}", other),
-            };
+/// Parses a single Operand or Call, a negated Expression or an Expression in Parentheses
+fn parse_unary<I>(tok: Token, tokens: &mut ScopeIter<I>) -> Result<Expression, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    let expected = [TokenNames::Literal, TokenNames::OpenParen, TokenNames::Bang];
 
-            let args = parse_args(tokens);
+    match &tok.0 {
+        TokenData::Literal(_) if matches!(tokens.peek(), Some(Token(TokenData::OpenParen, _))) => {
+            tokens.next();
+            let arguments = parse_list(tokens, parse_argument)?;
 
-            BehaviourValue::Operation {
-                name: name_tok,
-                arguments: args,
+            Ok(Expression::Call {
+                span: tok.1.join(tokens.last_span()),
+                name: tok,
+                parameters: Vec::new(),
+                arguments,
+            })
+        }
+        TokenData::Literal(_) if starts_parameters(tokens) => {
+            let mut parameters = Vec::new();
+            while let Some(Token(TokenData::Less | TokenData::Comma, _)) = tokens.next() {
+                let first = tokens.next().unwrap();
+                parameters.push(parse_number(first, tokens)?);
             }
+            expect_token(tokens, TokenNames::OpenParen)?;
+            let arguments = parse_list(tokens, parse_argument)?;
+
+            Ok(Expression::Call {
+                span: tok.1.join(tokens.last_span()),
+                name: tok,
+                parameters,
+                arguments,
+            })
+        }
+        TokenData::Literal(_) => parse_operand(tok, tokens).map(Expression::Operand),
+        TokenData::Text(_) => Ok(Expression::Text(tok)),
+        TokenData::Bang => {
+            let next_tok = next_token(tokens, &expected)?;
+            let inner = parse_unary(next_tok, tokens)?;
+
+            Ok(Expression::Not {
+                span: tok.1.join(inner.span()),
+                inner: Box::new(inner),
+            })
         }
         TokenData::OpenParen => {
-            let args = parse_args(tokens);
+            let next_tok = next_token(tokens, &expected)?;
+            let inner = parse_expression(next_tok, tokens)?;
+            expect_token(tokens, TokenNames::CloseParen)?;
 
-            BehaviourValue::Variables { vars: args }
+            Ok(inner)
         }
-        other => panic!("Unexpected Token: {:?}", other),
+        _ => Err(Error::UnexpectedToken {
+            expected: expected.to_vec(),
+            got: tok,
+        }),
     }
 }
 
-#[derive(Debug)]
+/// Parses the Binary Operators following the already parsed `left` Expression, as long as their
+/// Precedence is at least `min_precedence`
+fn parse_binary<I>(
+    mut left: Expression,
+    min_precedence: u8,
+    tokens: &mut ScopeIter<I>,
+) -> Result<Expression, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    while let Some(precedence) = tokens.peek().and_then(|t| binary_precedence(&t.0)) {
+        if precedence < min_precedence {
+            break;
+        }
+        let op = tokens.next().unwrap();
+
+        let next_tok = next_token(
+            tokens,
+            &[TokenNames::Literal, TokenNames::OpenParen, TokenNames::Bang],
+        )?;
+        let first = parse_unary(next_tok, tokens)?;
+        let right = parse_binary(first, precedence + 1, tokens)?;
+
+        left = Expression::Binary {
+            span: left.span().join(right.span()),
+            op,
+            left: Box::new(left),
+            right: Box::new(right),
+        };
+    }
+
+    Ok(left)
+}
+
+fn parse_expression<I>(tok: Token, tokens: &mut ScopeIter<I>) -> Result<Expression, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    let first = parse_unary(tok, tokens)?;
+    parse_binary(first, 0, tokens)
+}
+
+/// The Values on the right Side of a Statement
+#[derive(Debug, Clone)]
+pub struct BehaviourValue {
+    pub values: Vec<Expression>,
+}
+
+fn parse_value<I>(tokens: &mut ScopeIter<I>) -> Result<BehaviourValue, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    let init_token = next_token(
+        tokens,
+        &[TokenNames::Literal, TokenNames::OpenParen, TokenNames::Bang],
+    )?;
+
+    if !matches!(init_token.0, TokenData::OpenParen) {
+        let value = parse_expression(init_token, tokens)?;
+
+        return Ok(BehaviourValue {
+            values: vec![value],
+        });
+    }
+
+    let mut values = parse_list(tokens, parse_expression)?;
+
+    // A single parenthesized Value may also be the Start of a larger Expression
+    let continues = tokens
+        .peek()
+        .and_then(|t| binary_precedence(&t.0))
+        .is_some();
+    if continues && values.len() == 1 {
+        let first = values.remove(0);
+        values.push(parse_binary(first, 0, tokens)?);
+    }
+
+    Ok(BehaviourValue { values })
+}
+
+/// A single Row of a Truth-Table, where every Cell is either a `-` or a Literal, which may
+/// contain the Values of multiple Bits like `010`
+#[derive(Debug, Clone)]
+pub struct TableRow {
+    pub inputs: Vec<Token>,
+    pub outputs: Vec<Token>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
 pub enum BehaviourStatement {
     PortAssign {
-        targets: Vec<Token>,
+        targets: Vec<Target>,
         value: BehaviourValue,
+        span: Span,
     },
     VarAssign {
-        targets: Vec<Token>,
+        targets: Vec<Target>,
         value: BehaviourValue,
+        span: Span,
+    },
+    /// `table (a, b) -> (y) { 0 1 => 1; ... }`, where the Span only covers the Header of the
+    /// Table up to its Targets
+    Table {
+        inputs: Vec<Expression>,
+        targets: Vec<Operand>,
+        rows: Vec<TableRow>,
+        span: Span,
     },
+    /// `for i in 0..N { ... }`, which repeats the Statements for every Value of the Variable from
+    /// the Start up to, but not including, the End
+    For {
+        var: Token,
+        start: Number,
+        end: Number,
+        body: Vec<BehaviourStatement>,
+    },
+    /// `if N > 4 { ... } else { ... }`, which only keeps the Statements of the Branch selected by
+    /// the Condition
+    If {
+        condition: Condition,
+        body: Vec<BehaviourStatement>,
+        else_body: Vec<BehaviourStatement>,
+    },
+}
+
+/// The Comparison of two Numbers in an `if`, where the Operator is a `<`, `>` or `==`
+#[derive(Debug, Clone)]
+pub struct Condition {
+    pub left: Number,
+    pub op: Token,
+    pub right: Number,
 }
 
-fn parse_behaviour<I>(mut tokens: ScopeIter<I>) -> Vec<BehaviourStatement>
+fn parse_statement<I>(tok: Token, tokens: &mut ScopeIter<I>) -> Result<BehaviourStatement, Error>
 where
     I: Iterator<Item = Token>,
 {
-    let mut result = Vec::new();
+    match tok.0 {
+        TokenData::OpenParen => {
+            let targets = parse_targets(tokens)?;
 
-    while let Some(tok) = tokens.next() {
+            let next_tok = next_token(tokens, &[TokenNames::Assign, TokenNames::PortAssign])?;
+            match next_tok.0 {
+                TokenData::Assign => {
+                    let value = parse_value(tokens)?;
+
+                    let ending_tok = expect_token(tokens, TokenNames::Semicolon)?;
+                    let span = tok.1.join(&ending_tok.1);
+
+                    Ok(BehaviourStatement::VarAssign {
+                        targets,
+                        value,
+                        span,
+                    })
+                }
+                TokenData::PortAssign => {
+                    let value = parse_value(tokens)?;
+
+                    let ending_tok = expect_token(tokens, TokenNames::Semicolon)?;
+                    let span = tok.1.join(&ending_tok.1);
+
+                    Ok(BehaviourStatement::PortAssign {
+                        targets,
+                        value,
+                        span,
+                    })
+                }
+                _ => Err(Error::UnexpectedToken {
+                    expected: vec![TokenNames::Assign, TokenNames::PortAssign],
+                    got: next_tok,
+                }),
+            }
+        }
+        _ => Err(Error::UnexpectedToken {
+            expected: vec![
+                TokenNames::OpenParen,
+                TokenNames::Table,
+                TokenNames::For,
+                TokenNames::If,
+            ],
+            got: tok,
+        }),
+    }
+}
+
+/// Parses the Cells of one Side of a Row, starting with the given Token, up to and including the
+/// Token that ends the Side
+fn parse_table_cells<I>(
+    mut tok: Token,
+    tokens: &mut ScopeIter<I>,
+    end: TokenNames,
+) -> Result<(Vec<Token>, Token), Error>
+where
+    I: Iterator<Item = Token>,
+{
+    let expected = [TokenNames::Literal, TokenNames::Minus, end];
+    let mut cells = Vec::new();
+
+    loop {
         match tok.0 {
-            TokenData::OpenParen => {
-                let targets = parse_args(&mut tokens);
+            TokenData::Literal(_) | TokenData::Minus => cells.push(tok),
+            ref data if end.matches(data) => return Ok((cells, tok)),
+            _ => {
+                return Err(Error::UnexpectedToken {
+                    expected: expected.to_vec(),
+                    got: tok,
+                })
+            }
+        };
 
-                let next_tok = tokens.next().unwrap();
-                match next_tok.0 {
-                    TokenData::Assign => {
-                        let value = parse_value(&mut tokens);
+        tok = next_token(tokens, &expected)?;
+    }
+}
 
-                        let ending_tok = tokens.next().unwrap();
-                        match ending_tok.0 {
-                            TokenData::Semicolon => {}
-                            other => panic!("Expected Semicolon, got {:?}", other),
-                        };
+fn parse_table_row<I>(tok: Token, tokens: &mut ScopeIter<I>) -> Result<TableRow, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    let start = tok.1.clone();
+    let (inputs, _) = parse_table_cells(tok, tokens, TokenNames::FatArrow)?;
 
-                        result.push(BehaviourStatement::VarAssign { targets, value });
-                    }
-                    TokenData::PortAssign => {
-                        let value = parse_value(&mut tokens);
+    let first = next_token(
+        tokens,
+        &[
+            TokenNames::Literal,
+            TokenNames::Minus,
+            TokenNames::Semicolon,
+        ],
+    )?;
+    let (outputs, ending_tok) = parse_table_cells(first, tokens, TokenNames::Semicolon)?;
 
-                        let ending_tok = tokens.next().unwrap();
-                        match ending_tok.0 {
-                            TokenData::Semicolon => {}
-                            other => panic!("Expected Semicolon, got {:?}", other),
-                        };
+    Ok(TableRow {
+        inputs,
+        outputs,
+        span: start.join(&ending_tok.1),
+    })
+}
 
-                        result.push(BehaviourStatement::PortAssign { targets, value });
-                    }
-                    other => panic!("Unexpected Token: {:?}", other),
-                };
+/// Parses a Truth-Table after the `table` Keyword. Errors in the Rows are collected, so that
+/// parsing continues with the next Row
+fn parse_table<I>(
+    tok: Token,
+    tokens: &mut ScopeIter<I>,
+    errors: &mut Vec<Error>,
+) -> Result<BehaviourStatement, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    expect_token(tokens, TokenNames::OpenParen)?;
+    let inputs = parse_list(tokens, parse_expression)?;
+
+    expect_token(tokens, TokenNames::Arrow)?;
+    expect_token(tokens, TokenNames::OpenParen)?;
+    let targets = parse_list(tokens, parse_operand)?;
+    let span = tok.1.join(tokens.last_span());
+
+    let open_tok = expect_token(tokens, TokenNames::OpenCurly)?;
+    let mut body = ScopeIter::new(tokens.by_ref(), open_tok.1);
+
+    let mut rows = Vec::new();
+    while let Some(tok) = body.next() {
+        if let TokenData::DocComment(_) = tok.0 {
+            continue;
+        }
+
+        match parse_table_row(tok, &mut body) {
+            Ok(row) => rows.push(row),
+            Err(e) => {
+                synchronize(&mut body, &e);
+                errors.push(e);
             }
-            other => panic!("Unexpected Token: {:?}", other),
         };
     }
 
-    result
+    Ok(BehaviourStatement::Table {
+        inputs,
+        targets,
+        rows,
+        span,
+    })
+}
+
+/// The Tokens of a Behaviour-Block. The Iterator is dynamically dispatched, as the nested Blocks
+/// of the Generate-Statements would otherwise need infinitely many Types
+type BlockTokens<'t> = ScopeIter<&'t mut dyn Iterator<Item = Token>>;
+
+/// Parses the Statements of a Block, starting with its opening Curly
+fn parse_block(
+    tokens: &mut BlockTokens,
+    errors: &mut Vec<Error>,
+) -> Result<Vec<BehaviourStatement>, Error> {
+    let open_tok = expect_token(tokens, TokenNames::OpenCurly)?;
+    let body = ScopeIter::new(tokens as &mut dyn Iterator<Item = Token>, open_tok.1);
+
+    Ok(parse_behaviour(body, errors))
 }
 
-fn parse_entity<I>(name: Span, mut tokens: ScopeIter<I>) -> Result<Entity, Error>
+/// Parses a Generate-Loop after the `for` Keyword
+fn parse_for(
+    tokens: &mut BlockTokens,
+    errors: &mut Vec<Error>,
+) -> Result<BehaviourStatement, Error> {
+    let var = expect_token(tokens, TokenNames::Literal)?;
+    expect_token(tokens, TokenNames::In)?;
+
+    let first = next_token(tokens, &[TokenNames::Literal])?;
+    let start = parse_number(first, tokens)?;
+    expect_token(tokens, TokenNames::DotDot)?;
+    let first = next_token(tokens, &[TokenNames::Literal])?;
+    let end = parse_number(first, tokens)?;
+
+    let body = parse_block(tokens, errors)?;
+
+    Ok(BehaviourStatement::For {
+        var,
+        start,
+        end,
+        body,
+    })
+}
+
+/// Parses a Generate-Condition after the `if` Keyword, including an optional `else` Block
+fn parse_if(
+    tokens: &mut BlockTokens,
+    errors: &mut Vec<Error>,
+) -> Result<BehaviourStatement, Error> {
+    let first = next_token(tokens, &[TokenNames::Literal])?;
+    let left = parse_number(first, tokens)?;
+
+    let comparisons = [TokenNames::Less, TokenNames::Greater, TokenNames::Equals];
+    let op = next_token(tokens, &comparisons)?;
+    if !matches!(
+        op.0,
+        TokenData::Less | TokenData::Greater | TokenData::Equals
+    ) {
+        return Err(Error::UnexpectedToken {
+            expected: comparisons.to_vec(),
+            got: op,
+        });
+    }
+
+    let first = next_token(tokens, &[TokenNames::Literal])?;
+    let right = parse_number(first, tokens)?;
+
+    let body = parse_block(tokens, errors)?;
+    let mut else_body = Vec::new();
+    if matches!(tokens.peek(), Some(Token(TokenData::Else, _))) {
+        tokens.next();
+        else_body = parse_block(tokens, errors)?;
+    }
+
+    Ok(BehaviourStatement::If {
+        condition: Condition { left, op, right },
+        body,
+        else_body,
+    })
+}
+
+/// Skips the Rest of a Generate-Block after an Error in its Header, including its Body
+fn skip_generate<I>(tokens: &mut ScopeIter<I>, error: &Error)
 where
     I: Iterator<Item = Token>,
 {
-    let mut entity = Entity {
-        name,
-        in_ports: Vec::new(),
-        out_ports: Vec::new(),
-        behaviour: Vec::new(),
+    match error {
+        Error::UnexpectedToken {
+            got: Token(TokenData::OpenCurly, open),
+            ..
+        } => ScopeIter::new(tokens.by_ref(), open.clone()).for_each(drop),
+        Error::UnexpectedToken { .. } => skip_section(tokens),
+        Error::UnexpectedEnd { .. } => {}
     };
+}
 
-    while let Some(tok) = tokens.next() {
-        match tok.0 {
-            TokenData::InPorts => {
-                let next_tok = tokens.next().unwrap();
-                match next_tok.0 {
-                    TokenData::OpenCurly => {}
-                    other => panic!("Expected Open Curly, got {:?}", other),
-                };
+fn parse_behaviour(mut tokens: BlockTokens, errors: &mut Vec<Error>) -> Vec<BehaviourStatement> {
+    let mut result = Vec::new();
 
-                let tokens = ScopeIter::new(tokens.by_ref());
-                let in_ports = parse_ports(tokens);
+    while let Some(tok) = tokens.next() {
+        // Doc-Comments only document Entities and Ports
+        if let TokenData::DocComment(_) = tok.0 {
+            continue;
+        }
 
-                entity.in_ports = in_ports;
+        let generate = matches!(tok.0, TokenData::For | TokenData::If);
+        let statement = match tok.0 {
+            TokenData::Table => parse_table(tok, &mut tokens, errors),
+            TokenData::For => parse_for(&mut tokens, errors),
+            TokenData::If => parse_if(&mut tokens, errors),
+            _ => parse_statement(tok, &mut tokens),
+        };
+        match statement {
+            Ok(stmnt) => result.push(stmnt),
+            Err(e) if generate => {
+                skip_generate(&mut tokens, &e);
+                errors.push(e);
             }
-            TokenData::OutPorts => {
-                let next_tok = tokens.next().unwrap();
-                match next_tok.0 {
-                    TokenData::OpenCurly => {}
-                    other => panic!("Expected Open Curly, got {:?}", other),
-                };
+            Err(e) => {
+                synchronize(&mut tokens, &e);
+                errors.push(e);
+            }
+        };
+    }
 
-                let tokens = ScopeIter::new(tokens.by_ref());
-                let out_ports = parse_ports(tokens);
+    result
+}
 
-                entity.out_ports = out_ports;
+/// Skips the Tokens after an unexpected Token in an Entity, up to and including the next Block
+/// or Semicolon, as that most likely was the misspelled Section
+fn skip_section<I>(tokens: &mut ScopeIter<I>)
+where
+    I: Iterator<Item = Token>,
+{
+    while let Some(tok) = tokens.next() {
+        match tok.0 {
+            TokenData::OpenCurly => {
+                ScopeIter::new(tokens.by_ref(), tok.1).for_each(drop);
+                return;
             }
-            TokenData::Behaviour => {
-                let next_tok = tokens.next().unwrap();
-                match next_tok.0 {
-                    TokenData::OpenCurly => {}
-                    other => panic!("Expected Open Curly, got {:?}", other),
-                };
+            TokenData::Semicolon => return,
+            _ => {}
+        };
+    }
+}
 
-                let tokens = ScopeIter::new(tokens.by_ref());
-                let behaviour = parse_behaviour(tokens);
+fn parse_entity<I>(
+    name: Span,
+    params: Vec<Param>,
+    doc: Option<String>,
+    mut tokens: ScopeIter<I>,
+    errors: &mut Vec<Error>,
+) -> Entity
+where
+    I: Iterator<Item = Token>,
+{
+    let mut entity = Entity {
+        name,
+        params,
+        doc,
+        in_ports: Vec::new(),
+        out_ports: Vec::new(),
+        behaviour: Vec::new(),
+    };
 
-                entity.behaviour = behaviour;
-            }
+    while let Some(tok) = tokens.next() {
+        let section = match tok.0 {
+            TokenData::InPorts | TokenData::OutPorts | TokenData::Behaviour => tok,
+            TokenData::DocComment(_) => continue,
             _ => {
-                return Err(Error::UnexpectedToken {
+                errors.push(Error::UnexpectedToken {
                     expected: vec![
                         TokenNames::InPorts,
                         TokenNames::OutPorts,
                         TokenNames::Behaviour,
                     ],
                     got: tok,
-                })
+                });
+                skip_section(&mut tokens);
+                continue;
+            }
+        };
+
+        let open_tok = match expect_token(&mut tokens, TokenNames::OpenCurly) {
+            Ok(t) => t,
+            Err(e) => {
+                errors.push(e);
+                continue;
             }
         };
+        let section_tokens =
+            ScopeIter::new(&mut tokens as &mut dyn Iterator<Item = Token>, open_tok.1);
+
+        match section.0 {
+            TokenData::InPorts => {
+                entity.in_ports = parse_ports(section_tokens, errors);
+            }
+            TokenData::OutPorts => {
+                entity.out_ports = parse_ports(section_tokens, errors);
+            }
+            TokenData::Behaviour => {
+                entity.behaviour = parse_behaviour(section_tokens, errors);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    entity
+}
+
+/// Returns the next Token, if it is of the expected Kind, where the `previous` Span is used for
+/// the Error at the End of the Tokens
+fn expect_next<I>(
+    tokens: &mut Peekable<I>,
+    previous: &Span,
+    expected: &[TokenNames],
+) -> Result<Token, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    let tok = tokens.next().ok_or_else(|| Error::UnexpectedEnd {
+        expected: expected.to_vec(),
+        end: previous.clone(),
+    })?;
+
+    if expected.iter().any(|e| e.matches(&tok.0)) {
+        Ok(tok)
+    } else {
+        Err(Error::UnexpectedToken {
+            expected: expected.to_vec(),
+            got: tok,
+        })
+    }
+}
+
+/// Parses the Parameters of a generic Entity after the opening `<`, like `N: int, M: int>`
+fn parse_params<I>(open: Token, tokens: &mut Peekable<I>) -> Result<(Vec<Param>, Token), Error>
+where
+    I: Iterator<Item = Token>,
+{
+    let mut params = Vec::new();
+    let mut previous = open;
+
+    loop {
+        let name = expect_next(tokens, &previous.1, &[TokenNames::Literal])?;
+        let colon = expect_next(tokens, &name.1, &[TokenNames::Colon])?;
+        let ty = expect_next(tokens, &colon.1, &[TokenNames::Literal])?;
+
+        let next_tok = expect_next(tokens, &ty.1, &[TokenNames::Comma, TokenNames::Greater])?;
+        params.push(Param { name, ty });
+
+        match next_tok.0 {
+            TokenData::Comma => previous = next_tok,
+            _ => return Ok((params, next_tok)),
+        }
+    }
+}
+
+fn parse_entity_start<I>(
+    entity_tok: Token,
+    tokens: &mut Peekable<I>,
+) -> Result<(Span, Vec<Param>, Token), Error>
+where
+    I: Iterator<Item = Token>,
+{
+    let name_tok = tokens.next().ok_or_else(|| Error::UnexpectedEnd {
+        expected: vec![TokenNames::Literal],
+        end: entity_tok.1.clone(),
+    })?;
+    let entity_name = match name_tok.0 {
+        TokenData::Literal(_) => name_tok.1,
+        _ => {
+            return Err(Error::UnexpectedToken {
+                expected: vec![TokenNames::Literal],
+                got: name_tok,
+            })
+        }
+    };
+
+    let mut next_tok = tokens.next().ok_or_else(|| Error::UnexpectedEnd {
+        expected: vec![TokenNames::OpenCurly],
+        end: entity_name.clone(),
+    })?;
+
+    let mut params = Vec::new();
+    if let TokenData::Less = next_tok.0 {
+        let (parsed, close) = parse_params(next_tok, tokens)?;
+        params = parsed;
+        next_tok = expect_next(tokens, &close.1, &[TokenNames::OpenCurly])?;
     }
 
-    Ok(entity)
+    match next_tok.0 {
+        TokenData::OpenCurly => {}
+        _ => {
+            return Err(Error::UnexpectedToken {
+                expected: vec![TokenNames::OpenCurly],
+                got: next_tok,
+            })
+        }
+    };
+
+    Ok((entity_name, params, next_tok))
+}
+
+/// Parses a `use "file.mcl";` Statement after the `use` Keyword
+fn parse_use<I>(use_tok: Token, tokens: &mut Peekable<I>) -> Result<Use, Error>
+where
+    I: Iterator<Item = Token>,
+{
+    let path = tokens.next().ok_or_else(|| Error::UnexpectedEnd {
+        expected: vec![TokenNames::Text],
+        end: use_tok.1.clone(),
+    })?;
+    if !TokenNames::Text.matches(&path.0) {
+        return Err(Error::UnexpectedToken {
+            expected: vec![TokenNames::Text],
+            got: path,
+        });
+    }
+
+    let end_tok = tokens.next().ok_or_else(|| Error::UnexpectedEnd {
+        expected: vec![TokenNames::Semicolon],
+        end: path.1.clone(),
+    })?;
+    if !TokenNames::Semicolon.matches(&end_tok.0) {
+        return Err(Error::UnexpectedToken {
+            expected: vec![TokenNames::Semicolon],
+            got: end_tok,
+        });
+    }
+
+    Ok(Use { path })
 }
 
-pub fn parse(tokens: Vec<Token>) -> Result<Vec<Entity>, Error> {
-    let mut tokens = tokens.into_iter();
+/// Parses all the Uses and Entities in the Tokens.
+///
+/// Parsing continues after an Error, so the returned File may be incomplete and all the
+/// Errors found in the Tokens are returned together
+pub fn parse(tokens: Vec<Token>) -> (File, Vec<Error>) {
+    let mut tokens = tokens.into_iter().peekable();
 
+    let mut uses = Vec::new();
     let mut entities = Vec::new();
+    let mut errors = Vec::new();
+    let mut doc_lines = Vec::new();
 
     while let Some(tok) = tokens.next() {
+        if let TokenData::DocComment(text) = tok.0 {
+            doc_lines.push(text);
+            continue;
+        }
+
+        let doc = doc_string(std::mem::take(&mut doc_lines));
         match tok.0 {
             TokenData::Entity => {
-                let name_tok = tokens.next().ok_or(Error::MissingEntityName)?;
-                let entity_name = match name_tok.0 {
-                    TokenData::Literal(_) => name_tok.1,
-                    _ => {
-                        return Err(Error::UnexpectedToken {
-                            expected: vec![TokenNames::Literal],
-                            got: name_tok,
-                        })
-                    }
-                };
-
-                let next_tok = tokens.next().unwrap();
-                match next_tok.0 {
-                    TokenData::OpenCurly => {}
-                    _ => {
-                        return Err(Error::UnexpectedToken {
-                            expected: vec![TokenNames::OpenCurly],
-                            got: next_tok,
-                        })
+                let (entity_name, params, open_tok) = match parse_entity_start(tok, &mut tokens) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        errors.push(e);
+                        skip_to_item(&mut tokens);
+                        continue;
                     }
                 };
 
-                let in_scope_iter = ScopeIter::new(tokens.by_ref());
-                let entity = parse_entity(entity_name, in_scope_iter)?;
+                let in_scope_iter = ScopeIter::new(tokens.by_ref(), open_tok.1);
+                let entity = parse_entity(entity_name, params, doc, in_scope_iter, &mut errors);
 
                 entities.push(entity);
             }
-            other => {
-                dbg!(&other);
-                todo!()
+            TokenData::Use => match parse_use(tok, &mut tokens) {
+                Ok(u) => uses.push(u),
+                Err(e) => {
+                    errors.push(e);
+                    skip_to_item(&mut tokens);
+                }
+            },
+            _ => {
+                errors.push(Error::UnexpectedToken {
+                    expected: vec![TokenNames::Entity, TokenNames::Use],
+                    got: tok,
+                });
+                skip_to_item(&mut tokens);
             }
         };
     }
 
-    Ok(entities)
+    (File { uses, entities }, errors)
+}
+
+/// Skips all the Tokens until the next `entity` or `use` Keyword
+fn skip_to_item<I>(tokens: &mut Peekable<I>)
+where
+    I: Iterator<Item = Token>,
+{
+    while tokens
+        .next_if(|t| !matches!(t.0, TokenData::Entity | TokenData::Use))
+        .is_some()
+    {}
 }
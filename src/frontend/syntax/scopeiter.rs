@@ -1,28 +1,72 @@
-use crate::frontend::tokens::{Token, TokenData};
+use std::collections::VecDeque;
+
+use crate::frontend::{
+    tokens::{Token, TokenData},
+    Span,
+};
 
 pub struct ScopeIter<I> {
     iter: I,
     level: usize,
+    last: Span,
+    /// The Span of the last Token read from the underlying Iterator, which is ahead of `last`
+    /// while there are peeked Tokens
+    read: Span,
+    /// The Tokens that were peeked at but not consumed yet, together with the Span that `last`
+    /// becomes once they are consumed
+    peeked: VecDeque<(Option<Token>, Span)>,
 }
 
 impl<I> ScopeIter<I> {
-    pub fn new(iter: I) -> Self {
-        Self { iter, level: 1 }
+    /// Creates a new Scope, where the `start` is the Span of the Token that opened the Scope
+    pub fn new(iter: I, start: Span) -> Self {
+        Self {
+            iter,
+            level: 1,
+            last: start.clone(),
+            read: start,
+            peeked: VecDeque::new(),
+        }
+    }
+
+    /// The Span of the last Token consumed by the Scope, which is the closing Curly once the
+    /// Scope has ended
+    pub fn last_span(&self) -> &Span {
+        &self.last
     }
 }
 
-impl<I> Iterator for ScopeIter<I>
+impl<I> ScopeIter<I>
 where
     I: Iterator<Item = Token>,
 {
-    type Item = Token;
+    /// Returns the next Token in the Scope without consuming it
+    pub fn peek(&mut self) -> Option<&Token> {
+        self.peek_nth(0)
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Returns the Token `n` Tokens ahead in the Scope without consuming any of them
+    pub fn peek_nth(&mut self, n: usize) -> Option<&Token> {
+        while self.peeked.len() <= n {
+            let next = self.advance();
+            let ended = next.is_none();
+            self.peeked.push_back((next, self.read.clone()));
+
+            if ended {
+                break;
+            }
+        }
+
+        self.peeked.get(n).and_then(|(tok, _)| tok.as_ref())
+    }
+
+    fn advance(&mut self) -> Option<Token> {
         if self.level == 0 {
             return None;
         }
 
         let next = self.iter.next()?;
+        self.read = next.1.clone();
 
         match &next.0 {
             TokenData::OpenCurly => {
@@ -41,3 +85,24 @@ where
         Some(next)
     }
 }
+
+impl<I> Iterator for ScopeIter<I>
+where
+    I: Iterator<Item = Token>,
+{
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.peeked.pop_front() {
+            Some((peeked, span)) => {
+                self.last = span;
+                peeked
+            }
+            None => {
+                let next = self.advance();
+                self.last = self.read.clone();
+                next
+            }
+        }
+    }
+}
@@ -1,115 +1,269 @@
-use super::Span;
+use super::{Diagnostic, Span};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum TokenData {
     Entity,
     Behaviour,
     InPorts,
     OutPorts,
+    Table,
+    Use,
+    For,
+    In,
+    If,
+    Else,
     Literal(String),
+    /// A quoted Text like `"program.hex"`, containing the Text without the Quotes
+    Text(String),
     OpenCurly,
     CloseCurly,
     OpenParen,
     CloseParen,
+    OpenBracket,
+    CloseBracket,
     Comma,
     Colon,
     Semicolon,
     Assign,
     PortAssign,
+    Ampersand,
+    Pipe,
+    Caret,
+    Bang,
+    Plus,
+    Minus,
+    /// `<`, where `<=` is a Port-Assignment instead
+    Less,
+    /// `>`, which closes the Parameters of an Entity or compares Numbers in a Condition
+    Greater,
+    /// `==`
+    Equals,
+    /// `->`
+    Arrow,
+    /// `=>`
+    FatArrow,
+    /// `..`, which separates the Bounds of a Generate-Loop
+    DotDot,
+    /// `.`, which starts the Name of the Output in a Target like `.carry_out: c`
+    Dot,
+    /// A `///` Comment, containing the Text of the Comment
+    DocComment(String),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Token(pub TokenData, pub Span);
 
-pub fn tokenize(span: Span) -> Vec<Token> {
-    let content = span.content();
-    let mut chars = content.chars().enumerate().peekable();
+#[derive(Debug)]
+pub enum Error {
+    UnterminatedComment { start: Span },
+    UnterminatedText { start: Span },
+}
+
+impl Error {
+    pub fn diagnostic(&self) -> Diagnostic {
+        match self {
+            Self::UnterminatedComment { start } => {
+                Diagnostic::new(start.clone(), "unterminated block comment")
+            }
+            Self::UnterminatedText { start } => {
+                Diagnostic::new(start.clone(), "unterminated text, expected a closing `\"`")
+            }
+        }
+    }
+}
+
+fn literal_token(inner: &str, inner_span: Span) -> Token {
+    match inner {
+        "entity" => Token(TokenData::Entity, inner_span),
+        "behaviour" => Token(TokenData::Behaviour, inner_span),
+        "in_ports" => Token(TokenData::InPorts, inner_span),
+        "out_ports" => Token(TokenData::OutPorts, inner_span),
+        "table" => Token(TokenData::Table, inner_span),
+        "use" => Token(TokenData::Use, inner_span),
+        "for" => Token(TokenData::For, inner_span),
+        "in" => Token(TokenData::In, inner_span),
+        "if" => Token(TokenData::If, inner_span),
+        "else" => Token(TokenData::Else, inner_span),
+        _ => Token(TokenData::Literal(inner.to_string()), inner_span),
+    }
+}
+
+/// The Tokens that consist of only a single Character and also end any Literal before them
+fn single_char_token(c: char) -> Option<TokenData> {
+    match c {
+        ';' => Some(TokenData::Semicolon),
+        ':' => Some(TokenData::Colon),
+        ',' => Some(TokenData::Comma),
+        '(' => Some(TokenData::OpenParen),
+        ')' => Some(TokenData::CloseParen),
+        '[' => Some(TokenData::OpenBracket),
+        ']' => Some(TokenData::CloseBracket),
+        '{' => Some(TokenData::OpenCurly),
+        '}' => Some(TokenData::CloseCurly),
+        '=' => Some(TokenData::Assign),
+        '&' => Some(TokenData::Ampersand),
+        '|' => Some(TokenData::Pipe),
+        '^' => Some(TokenData::Caret),
+        '!' => Some(TokenData::Bang),
+        '+' => Some(TokenData::Plus),
+        '-' => Some(TokenData::Minus),
+        '<' => Some(TokenData::Less),
+        '>' => Some(TokenData::Greater),
+        '.' => Some(TokenData::Dot),
+        _ => None,
+    }
+}
+
+struct Lexer<'s> {
+    span: &'s Span,
+    content: &'s str,
+    result: Vec<Token>,
+    /// The Offset at which the Literal, that is currently being read, started
+    literal_start: Option<usize>,
+}
+
+impl<'s> Lexer<'s> {
+    /// Creates a Span for the given Range of Offsets into the Content of the Lexer
+    fn span(&self, area: std::ops::Range<usize>) -> Span {
+        let base = self.span.start();
+        self.span.sub_span(base + area.start..base + area.end)
+    }
+
+    fn push(&mut self, data: TokenData, area: std::ops::Range<usize>) {
+        let span = self.span(area);
+        self.result.push(Token(data, span));
+    }
+
+    /// Ends the current Literal, if there is one, right before the given Offset
+    fn end_literal(&mut self, end: usize) {
+        if let Some(start) = self.literal_start.take() {
+            let inner_span = self.span(start..end);
+            self.result
+                .push(literal_token(&self.content[start..end], inner_span));
+        }
+    }
+}
 
-    let mut result = Vec::new();
-    let mut start = 0;
+/// Splits the Content of the Span into Tokens.
+///
+/// All the Offsets are Byte-Offsets into the Content, so Non-ASCII Characters are handled
+/// correctly and any kind of Whitespace (including Tabs and `\r\n` Line-Endings) separates
+/// Tokens
+pub fn tokenize(span: Span) -> Result<Vec<Token>, Error> {
+    let mut lexer = Lexer {
+        span: &span,
+        content: span.content(),
+        result: Vec::new(),
+        literal_start: None,
+    };
+    let content = lexer.content;
+    let mut chars = content.char_indices().peekable();
 
     while let Some((i, tmp_c)) = chars.next() {
         match tmp_c {
-            '<' => {
-                let (_, peek_c) = chars.peek().unwrap();
-                match peek_c {
-                    '=' => {
-                        result.push(Token(TokenData::PortAssign, span.sub_span(i..i + 2)));
-                        let _ = chars.next();
-                        start = i + 2;
-                    }
-                    other => {
-                        dbg!(other);
-                        todo!()
-                    }
-                };
+            c if c.is_whitespace() => {
+                lexer.end_literal(i);
             }
-            '=' => {
-                result.push(Token(TokenData::Assign, span.sub_span(i..i + 1)));
-                start = i + 1;
+            '/' if matches!(chars.peek(), Some((_, '/'))) => {
+                lexer.end_literal(i);
+                let _ = chars.next();
+
+                let is_doc = chars.next_if(|(_, c)| *c == '/').is_some()
+                    && !matches!(chars.peek(), Some((_, '/')));
+
+                let end = chars
+                    .find(|(_, c)| *c == '\n')
+                    .map(|(j, _)| j)
+                    .unwrap_or(content.len());
+
+                if is_doc {
+                    let text = &content[i + 3..end];
+                    let text = text.strip_prefix(' ').unwrap_or(text).trim_end();
+                    lexer.push(TokenData::DocComment(text.to_string()), i..end);
+                }
             }
-            '\n' | ' ' | ';' | ':' | ',' | '(' | ')' | '{' | '}' => {
-                if i - start != 0 {
-                    let inner = &content[start..i];
-                    let inner_span = span.sub_span(start..i);
-                    match inner {
-                        "entity" => {
-                            result.push(Token(TokenData::Entity, inner_span));
-                        }
-                        "behaviour" => {
-                            result.push(Token(TokenData::Behaviour, inner_span));
-                        }
-                        "in_ports" => {
-                            result.push(Token(TokenData::InPorts, inner_span));
+            '/' if matches!(chars.peek(), Some((_, '*'))) => {
+                lexer.end_literal(i);
+                let _ = chars.next();
+
+                // Block Comments can be nested, so we need to find the matching End
+                let mut depth = 1;
+                while depth > 0 {
+                    match chars.next() {
+                        Some((_, '*')) if chars.next_if(|(_, c)| *c == '/').is_some() => {
+                            depth -= 1;
                         }
-                        "out_ports" => {
-                            result.push(Token(TokenData::OutPorts, inner_span));
+                        Some((_, '/')) if chars.next_if(|(_, c)| *c == '*').is_some() => {
+                            depth += 1;
                         }
-                        _ => {
-                            result.push(Token(
-                                TokenData::Literal(inner.to_string()),
-                                span.sub_span(start..i),
-                            ));
+                        Some(_) => {}
+                        None => {
+                            return Err(Error::UnterminatedComment {
+                                start: lexer.span(i..i + 2),
+                            })
                         }
                     };
                 }
+            }
+            '"' => {
+                lexer.end_literal(i);
 
-                let seperator = &content[i..i + 1];
-                match seperator {
-                    " " => {}
-                    "\n" => {}
-                    ";" => {
-                        result.push(Token(TokenData::Semicolon, span.sub_span(i..i + 1)));
-                    }
-                    ":" => {
-                        result.push(Token(TokenData::Colon, span.sub_span(i..i + 1)));
-                    }
-                    "," => {
-                        result.push(Token(TokenData::Comma, span.sub_span(i..i + 1)));
-                    }
-                    "(" => {
-                        result.push(Token(TokenData::OpenParen, span.sub_span(i..i + 1)));
-                    }
-                    ")" => {
-                        result.push(Token(TokenData::CloseParen, span.sub_span(i..i + 1)));
-                    }
-                    "{" => {
-                        result.push(Token(TokenData::OpenCurly, span.sub_span(i..i + 1)));
-                    }
-                    "}" => {
-                        result.push(Token(TokenData::CloseCurly, span.sub_span(i..i + 1)));
-                    }
-                    other => {
-                        dbg!(other);
-                        todo!("Unknown Seperator")
+                let end = match chars.find(|(_, c)| *c == '"') {
+                    Some((j, _)) => j,
+                    None => {
+                        return Err(Error::UnterminatedText {
+                            start: lexer.span(i..i + 1),
+                        })
                     }
                 };
 
-                start = i + 1;
+                let text = content[i + 1..end].to_string();
+                lexer.push(TokenData::Text(text), i..end + 1);
             }
-            _ => {}
+            '<' if matches!(chars.peek(), Some((_, '='))) => {
+                lexer.end_literal(i);
+                let _ = chars.next();
+
+                lexer.push(TokenData::PortAssign, i..i + 2);
+            }
+            '=' if matches!(chars.peek(), Some((_, '='))) => {
+                lexer.end_literal(i);
+                let _ = chars.next();
+
+                lexer.push(TokenData::Equals, i..i + 2);
+            }
+            '-' if matches!(chars.peek(), Some((_, '>'))) => {
+                lexer.end_literal(i);
+                let _ = chars.next();
+
+                lexer.push(TokenData::Arrow, i..i + 2);
+            }
+            '=' if matches!(chars.peek(), Some((_, '>'))) => {
+                lexer.end_literal(i);
+                let _ = chars.next();
+
+                lexer.push(TokenData::FatArrow, i..i + 2);
+            }
+            '.' if matches!(chars.peek(), Some((_, '.'))) => {
+                lexer.end_literal(i);
+                let _ = chars.next();
+
+                lexer.push(TokenData::DotDot, i..i + 2);
+            }
+            c => match single_char_token(c) {
+                Some(data) => {
+                    lexer.end_literal(i);
+                    lexer.push(data, i..i + c.len_utf8());
+                }
+                None => {
+                    if lexer.literal_start.is_none() {
+                        lexer.literal_start = Some(i);
+                    }
+                }
+            },
         };
     }
+    lexer.end_literal(content.len());
 
-    result
+    Ok(lexer.result)
 }
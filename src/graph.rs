@@ -1,4 +1,4 @@
-mod general;
+pub mod general;
 
 pub mod builtin;
 pub mod entity;
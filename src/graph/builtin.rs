@@ -13,14 +13,55 @@ pub enum BuiltinOp {
     Not,
     Xor,
     Or,
+    Nand,
+    Nor,
+    Xnor,
+    Dff,
+    Latch,
+    RsLatch,
+    /// Turns on for the given Number of Ticks on the rising Edge of its Input
+    Pulse {
+        length: u32,
+    },
+    /// A Read-Only Memory with a Word for every Address, where the first Input is the least
+    /// significant Bit of the Address and every Output is a single Bit of the Words
+    Rom {
+        address_width: u32,
+        data_width: u32,
+        words: Vec<u64>,
+    },
+    /// A Random-Access Memory with a Word for every Address. The Inputs are the Bits of the
+    /// Address, the Bits of the Data to write, the Write-Enable and the Clock, the Data is written
+    /// while both of the latter are on. Every Output is a single Bit of the addressed Word
+    Ram {
+        address_width: u32,
+        data_width: u32,
+    },
 }
 
 #[derive(Debug)]
 pub enum NodeType {
-    Input { name: String, number: u32 },
-    Output { name: String, number: u32 },
-    Variable { name: String },
-    BuiltinOp { op: BuiltinOp },
+    Input {
+        name: String,
+        number: u32,
+    },
+    Output {
+        name: String,
+        number: u32,
+    },
+    Variable {
+        name: String,
+    },
+    Constant {
+        value: bool,
+    },
+    /// Turns on and off with the given Period in Ticks
+    Clock {
+        period: u32,
+    },
+    BuiltinOp {
+        op: BuiltinOp,
+    },
 }
 
 impl Graph {
@@ -38,12 +79,37 @@ impl Graph {
                         normalized::NodeType::Output { name, number }
                     }
                     NodeType::Variable { name } => normalized::NodeType::Variable { name },
+                    NodeType::Constant { value } => normalized::NodeType::Constant { value },
+                    NodeType::Clock { period } => normalized::NodeType::Clock { period },
                     NodeType::BuiltinOp { op } => {
                         let tmp_op = match op {
                             BuiltinOp::And => normalized::BuiltinOp::And,
                             BuiltinOp::Not => normalized::BuiltinOp::Not,
                             BuiltinOp::Xor => normalized::BuiltinOp::Xor,
                             BuiltinOp::Or => normalized::BuiltinOp::Or,
+                            BuiltinOp::Nand => normalized::BuiltinOp::Nand,
+                            BuiltinOp::Nor => normalized::BuiltinOp::Nor,
+                            BuiltinOp::Xnor => normalized::BuiltinOp::Xnor,
+                            BuiltinOp::Dff => normalized::BuiltinOp::Dff,
+                            BuiltinOp::Latch => normalized::BuiltinOp::Latch,
+                            BuiltinOp::RsLatch => normalized::BuiltinOp::RsLatch,
+                            BuiltinOp::Pulse { length } => normalized::BuiltinOp::Pulse { length },
+                            BuiltinOp::Rom {
+                                address_width,
+                                data_width,
+                                words,
+                            } => normalized::BuiltinOp::Rom {
+                                address_width,
+                                data_width,
+                                words,
+                            },
+                            BuiltinOp::Ram {
+                                address_width,
+                                data_width,
+                            } => normalized::BuiltinOp::Ram {
+                                address_width,
+                                data_width,
+                            },
                         };
                         normalized::NodeType::Operation { op: tmp_op }
                     }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use super::builtin;
 
@@ -13,15 +13,70 @@ pub enum BuiltinOp {
     And,
     Xor,
     Or,
+    Nand,
+    Nor,
+    Xnor,
+    Dff,
+    Latch,
+    RsLatch,
+    /// Turns on for the given Number of Ticks on the rising Edge of its Input
+    Pulse {
+        length: u32,
+    },
+    /// A Read-Only Memory with a Word for every Address, where the first Input is the least
+    /// significant Bit of the Address and every Output is a single Bit of the Words
+    Rom {
+        address_width: u32,
+        data_width: u32,
+        words: Vec<u64>,
+    },
+    /// A Random-Access Memory with a Word for every Address. The Inputs are the Bits of the
+    /// Address, the Bits of the Data to write, the Write-Enable and the Clock, the Data is written
+    /// while both of the latter are on. Every Output is a single Bit of the addressed Word
+    Ram {
+        address_width: u32,
+        data_width: u32,
+    },
 }
 
 #[derive(Debug, Clone)]
 pub enum NodeType {
-    Input { name: String, number: u32 },
-    Output { name: String, number: u32 },
-    Variable { name: String },
-    BuiltinOp { op: BuiltinOp },
-    EntityOp { name: String },
+    Input {
+        name: String,
+        number: u32,
+    },
+    Output {
+        name: String,
+        number: u32,
+    },
+    Variable {
+        name: String,
+    },
+    Constant {
+        value: bool,
+    },
+    /// Turns on and off with the given Period in Ticks
+    Clock {
+        period: u32,
+    },
+    BuiltinOp {
+        op: BuiltinOp,
+    },
+    EntityOp {
+        name: String,
+    },
+}
+
+impl NodeType {
+    /// Whether the Node keeps its Value on its own, so a Loop through it is not combinational
+    pub fn is_storage(&self) -> bool {
+        matches!(
+            self,
+            NodeType::BuiltinOp {
+                op: BuiltinOp::Dff | BuiltinOp::Latch | BuiltinOp::RsLatch | BuiltinOp::Ram { .. }
+            }
+        )
+    }
 }
 
 impl Graph {
@@ -68,6 +123,15 @@ impl Graph {
     }
 
     pub fn into_builtin(self, entities: &HashMap<String, Graph>) -> super::builtin::Graph {
+        // The Ports of the inlined Entities are also Inputs and Outputs, so the ones of this
+        // Entity are remembered before inlining
+        let ports: Vec<u32> = self
+            .nodes
+            .iter()
+            .filter(|n| matches!(n.inner, NodeType::Input { .. } | NodeType::Output { .. }))
+            .map(|n| n.id)
+            .collect();
+
         let mut nodes = self.nodes;
         let mut edges = self.edges;
 
@@ -91,7 +155,14 @@ impl Graph {
 
             let mut replacement_graph = entities.get(name).unwrap().clone();
 
-            let max_id = nodes.iter().map(|n| n.id).max().unwrap() + 1;
+            // The replaced Node still has Edges, so its ID must not be reused
+            let max_id = nodes
+                .iter()
+                .map(|n| n.id)
+                .chain(std::iter::once(to_replace.id))
+                .max()
+                .unwrap()
+                + 1;
             replacement_graph.offset_ids(max_id);
 
             let input_ids = replacement_graph.inputs();
@@ -121,6 +192,8 @@ impl Graph {
                 });
         }
 
+        prune(&mut nodes, &mut edges, &ports);
+
         let n_nodes: Vec<_> = nodes
             .into_iter()
             .map(|n| {
@@ -128,6 +201,8 @@ impl Graph {
                     NodeType::Input { name, number } => builtin::NodeType::Input { name, number },
                     NodeType::Output { name, number } => builtin::NodeType::Output { name, number },
                     NodeType::Variable { name } => builtin::NodeType::Variable { name },
+                    NodeType::Constant { value } => builtin::NodeType::Constant { value },
+                    NodeType::Clock { period } => builtin::NodeType::Clock { period },
                     NodeType::EntityOp { .. } => panic!("Unexpected Entity Op"),
                     NodeType::BuiltinOp { op } => {
                         let n_op = match op {
@@ -135,6 +210,29 @@ impl Graph {
                             BuiltinOp::Not => builtin::BuiltinOp::Not,
                             BuiltinOp::Xor => builtin::BuiltinOp::Xor,
                             BuiltinOp::Or => builtin::BuiltinOp::Or,
+                            BuiltinOp::Nand => builtin::BuiltinOp::Nand,
+                            BuiltinOp::Nor => builtin::BuiltinOp::Nor,
+                            BuiltinOp::Xnor => builtin::BuiltinOp::Xnor,
+                            BuiltinOp::Dff => builtin::BuiltinOp::Dff,
+                            BuiltinOp::Latch => builtin::BuiltinOp::Latch,
+                            BuiltinOp::RsLatch => builtin::BuiltinOp::RsLatch,
+                            BuiltinOp::Pulse { length } => builtin::BuiltinOp::Pulse { length },
+                            BuiltinOp::Rom {
+                                address_width,
+                                data_width,
+                                words,
+                            } => builtin::BuiltinOp::Rom {
+                                address_width,
+                                data_width,
+                                words,
+                            },
+                            BuiltinOp::Ram {
+                                address_width,
+                                data_width,
+                            } => builtin::BuiltinOp::Ram {
+                                address_width,
+                                data_width,
+                            },
                         };
                         builtin::NodeType::BuiltinOp { op: n_op }
                     }
@@ -146,3 +244,25 @@ impl Graph {
         builtin::Graph::new(n_nodes, edges)
     }
 }
+
+/// Removes all the Nodes whose Values never reach one of the Ports, like the Logic behind the
+/// discarded Outputs of inlined Entities, so it doesn't get placed at all
+fn prune(nodes: &mut Vec<Node>, edges: &mut Vec<Edge>, ports: &[u32]) {
+    let mut sources: HashMap<u32, Vec<u32>> = HashMap::new();
+    for edge in edges.iter() {
+        sources.entry(edge.dest_id).or_default().push(edge.src_id);
+    }
+
+    let mut used: HashSet<u32> = ports.iter().copied().collect();
+    let mut pending: Vec<u32> = ports.to_vec();
+    while let Some(id) = pending.pop() {
+        for src in sources.get(&id).into_iter().flatten() {
+            if used.insert(*src) {
+                pending.push(*src);
+            }
+        }
+    }
+
+    nodes.retain(|n| used.contains(&n.id));
+    edges.retain(|e| used.contains(&e.src_id) && used.contains(&e.dest_id));
+}
@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, PartialEq)]
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Edge {
     pub src_id: u32,
     pub src_port: u32,
@@ -103,6 +105,79 @@ impl<T> Graph<T> {
     pub fn max_id(&self) -> u32 {
         self.nodes.iter().map(|n| n.id).max().unwrap()
     }
+
+    /// Returns the strongly connected Components of the Graph, as the IDs of the Nodes in them.
+    /// Every Node is part of exactly one Component and a Component with more than one Node, or
+    /// with an Edge from its only Node to itself, contains a Loop
+    pub fn strongly_connected_components(&self) -> Vec<Vec<u32>> {
+        let mut successors: HashMap<u32, Vec<u32>> = HashMap::new();
+        for edge in self.edges.iter() {
+            successors
+                .entry(edge.src_id)
+                .or_default()
+                .push(edge.dest_id);
+        }
+
+        // Tarjan's Algorithm, using an explicit Stack instead of Recursion
+        let mut indices: HashMap<u32, usize> = HashMap::new();
+        let mut low_links: HashMap<u32, usize> = HashMap::new();
+        let mut stack: Vec<u32> = Vec::new();
+        let mut on_stack: HashSet<u32> = HashSet::new();
+        let mut result = Vec::new();
+
+        for start in self.nodes.iter().map(|n| n.id) {
+            if indices.contains_key(&start) {
+                continue;
+            }
+
+            indices.insert(start, indices.len());
+            low_links.insert(start, indices[&start]);
+            stack.push(start);
+            on_stack.insert(start);
+
+            let mut call_stack = vec![(start, 0)];
+            while let Some((id, child)) = call_stack.pop() {
+                let succs = successors.get(&id).map(|s| s.as_slice()).unwrap_or(&[]);
+
+                if let Some(next) = succs.get(child).copied() {
+                    call_stack.push((id, child + 1));
+
+                    if !indices.contains_key(&next) {
+                        indices.insert(next, indices.len());
+                        low_links.insert(next, indices[&next]);
+                        stack.push(next);
+                        on_stack.insert(next);
+
+                        call_stack.push((next, 0));
+                    } else if on_stack.contains(&next) {
+                        let low = std::cmp::min(low_links[&id], indices[&next]);
+                        low_links.insert(id, low);
+                    }
+                    continue;
+                }
+
+                if let Some((parent, _)) = call_stack.last() {
+                    let low = std::cmp::min(low_links[parent], low_links[&id]);
+                    low_links.insert(*parent, low);
+                }
+
+                if low_links[&id] == indices[&id] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack.remove(&member);
+                        component.push(member);
+
+                        if member == id {
+                            break;
+                        }
+                    }
+                    result.push(component);
+                }
+            }
+        }
+
+        result
+    }
 }
 
 impl<T> Graph<T>
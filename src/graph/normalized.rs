@@ -1,3 +1,5 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
 pub use super::general::Edge;
 
 pub type Graph = super::general::Graph<NodeType>;
@@ -5,11 +7,31 @@ pub type Node = super::general::Node<NodeType>;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum NodeType {
-    Input { name: String, number: u32 },
-    Output { name: String, number: u32 },
-    Variable { name: String },
-    Splitter { port_count: u32 },
-    Operation { op: BuiltinOp },
+    Input {
+        name: String,
+        number: u32,
+    },
+    Output {
+        name: String,
+        number: u32,
+    },
+    Variable {
+        name: String,
+    },
+    /// A fixed Value, which is either always on or always off
+    Constant {
+        value: bool,
+    },
+    /// Turns on and off with the given Period in Ticks
+    Clock {
+        period: u32,
+    },
+    Splitter {
+        port_count: u32,
+    },
+    Operation {
+        op: BuiltinOp,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -18,17 +40,68 @@ pub enum BuiltinOp {
     And,
     Or,
     Not,
+    Nand,
+    Nor,
+    Xnor,
+    Dff,
+    Latch,
+    RsLatch,
+    /// Turns on for the given Number of Ticks on the rising Edge of its Input
+    Pulse {
+        length: u32,
+    },
+    /// A Read-Only Memory with a Word for every Address, where the first Input is the least
+    /// significant Bit of the Address and every Output is a single Bit of the Words
+    Rom {
+        address_width: u32,
+        data_width: u32,
+        words: Vec<u64>,
+    },
+    /// A Random-Access Memory with a Word for every Address. The Inputs are the Bits of the
+    /// Address, the Bits of the Data to write, the Write-Enable and the Clock, the Data is written
+    /// while both of the latter are on. Every Output is a single Bit of the addressed Word
+    Ram {
+        address_width: u32,
+        data_width: u32,
+    },
+}
+
+impl NodeType {
+    /// Whether the Node keeps its Value on its own, so a Loop through it is not combinational
+    pub fn is_storage(&self) -> bool {
+        matches!(
+            self,
+            NodeType::Operation {
+                op: BuiltinOp::Dff | BuiltinOp::Latch | BuiltinOp::RsLatch | BuiltinOp::Ram { .. }
+            }
+        )
+    }
+}
+
+/// The simplified Form of a Node with at least one Constant Input
+enum Folded {
+    /// The Node always produces the same Value
+    Constant(bool),
+    /// The Node simply forwards the Value from the Source
+    Forward(Edge),
+    /// The Node inverts the Value from the Source
+    Invert(Edge),
 }
 
 impl Graph {
     pub fn optimize(&mut self) {
+        self.fold_constants();
+
         let removeable: Vec<_> = self
             .nodes
             .iter()
             .filter(|n| {
                 matches!(
                     &n.inner,
-                    NodeType::Input { .. } | NodeType::Output { .. } | NodeType::Variable { .. }
+                    NodeType::Input { .. }
+                        | NodeType::Output { .. }
+                        | NodeType::Variable { .. }
+                        | NodeType::Splitter { .. }
                 )
             })
             .filter(|n| {
@@ -61,3 +134,397 @@ impl Graph {
         }
     }
 }
+
+impl Graph {
+    /// Returns the Edges that close a Loop in the Graph, so that the Graph without them is
+    /// acyclic. Loops are cut right after a Storage-Element, if they contain one, and otherwise
+    /// after an arbitrary Node of the Loop
+    pub fn feedback_edges(&self) -> HashSet<Edge> {
+        let mut feedback = HashSet::new();
+
+        // Cutting a Loop can leave smaller Loops in the same Component, so those are searched
+        // again in only what is left of the Component
+        let mut pending = vec![self.clone()];
+        while let Some(graph) = pending.pop() {
+            let components = graph.strongly_connected_components();
+
+            let mut component_of: HashMap<u32, usize> = HashMap::new();
+            for (index, component) in components.iter().enumerate() {
+                component_of.extend(component.iter().map(|id| (*id, index)));
+            }
+            let mut inner_edges: Vec<Vec<Edge>> = components.iter().map(|_| Vec::new()).collect();
+            for edge in graph.edges {
+                let index = component_of[&edge.src_id];
+                if component_of.get(&edge.dest_id) == Some(&index) {
+                    inner_edges[index].push(edge);
+                }
+            }
+
+            for (component, edges) in components.into_iter().zip(inner_edges) {
+                if edges.is_empty() {
+                    continue;
+                }
+
+                let members: HashSet<u32> = component.iter().copied().collect();
+                let nodes: Vec<Node> = graph
+                    .nodes
+                    .iter()
+                    .filter(|n| members.contains(&n.id))
+                    .cloned()
+                    .collect();
+                let cut_id = nodes
+                    .iter()
+                    .find(|n| n.inner.is_storage())
+                    .map(|n| n.id)
+                    .unwrap_or_else(|| *component.iter().min().unwrap());
+
+                let (cut, rest): (Vec<_>, Vec<_>) =
+                    edges.into_iter().partition(|e| e.src_id == cut_id);
+                feedback.extend(cut);
+                pending.push(Graph::new(nodes, rest));
+            }
+        }
+
+        feedback
+    }
+
+    /// Returns the Loops in the Graph, that do not pass through any Storage-Element, so their
+    /// Value never settles. Every Loop is described by the sorted Names of the Variables and Ports
+    /// in it, where the Bits of a Bus are named once for the whole Bus
+    pub fn combinational_loops(&self) -> Vec<Vec<String>> {
+        let mut loops = Vec::new();
+        for component in self.strongly_connected_components() {
+            let is_loop = component.len() > 1
+                || self
+                    .edges
+                    .iter()
+                    .any(|e| e.src_id == component[0] && e.dest_id == component[0]);
+            if !is_loop {
+                continue;
+            }
+
+            let nodes: Vec<_> = component
+                .iter()
+                .filter_map(|id| self.get_node(*id).map(|n| &n.inner))
+                .collect();
+            if nodes.iter().any(|n| n.is_storage()) {
+                continue;
+            }
+
+            let mut names: Vec<String> = nodes
+                .into_iter()
+                .filter_map(|n| match n {
+                    NodeType::Input { name, .. }
+                    | NodeType::Output { name, .. }
+                    | NodeType::Variable { name } => {
+                        Some(name.split('[').next().unwrap_or(name).to_string())
+                    }
+                    _ => None,
+                })
+                .collect();
+            names.sort();
+            names.dedup();
+            loops.push(names);
+        }
+
+        loops
+    }
+}
+
+impl Graph {
+    /// Replaces all the Nodes, whose Value is fully or partially determined by Constant Inputs,
+    /// with simpler Nodes, until no more Nodes can be simplified
+    fn fold_constants(&mut self) {
+        let mut folding = Folding::new(
+            std::mem::take(&mut self.nodes),
+            std::mem::take(&mut self.edges),
+        );
+
+        // Only Nodes with a Constant Input can be simplified, which they only get from the
+        // Constants that exist from the Start or from simplifying the Nodes in front of them.
+        // The first Node in the Graph is simplified first, so the Result doesn't depend on the
+        // Order in which the Nodes became simplifiable
+        let mut pending: BTreeSet<usize> = folding
+            .nodes
+            .iter()
+            .flatten()
+            .filter(|n| matches!(n.inner, NodeType::Constant { .. }))
+            .flat_map(|n| folding.outputs(n.id))
+            .filter_map(|e| folding.positions.get(&e.dest_id).copied())
+            .collect();
+
+        while let Some(position) = pending.pop_first() {
+            let node = match &folding.nodes[position] {
+                Some(node) => node,
+                None => continue,
+            };
+            let id = node.id;
+            let folded = match folding.fold(node) {
+                Some(folded) => folded,
+                None => continue,
+            };
+
+            let inputs = folding.inputs(id);
+            let outputs = folding.outputs(id);
+
+            let mut added = Vec::new();
+            match folded {
+                Folded::Constant(value) => {
+                    folding.remove_node(id);
+
+                    // Every Target gets its own Constant, so no Splitters are needed
+                    for output in outputs {
+                        let c_id = folding.add_node(NodeType::Constant { value });
+                        added.push(Edge::new(c_id, 0, output.dest_id, output.dest_port));
+                    }
+                }
+                Folded::Forward(source) => {
+                    folding.remove_node(id);
+
+                    for output in outputs {
+                        added.push(Edge::new(
+                            source.src_id,
+                            source.src_port,
+                            output.dest_id,
+                            output.dest_port,
+                        ));
+                    }
+                }
+                Folded::Invert(source) => {
+                    for edge in inputs {
+                        folding.remove_edge(&edge);
+                    }
+
+                    if let Some(node) = folding.nodes[position].as_mut() {
+                        node.inner = NodeType::Operation { op: BuiltinOp::Not };
+                    }
+                    added.push(Edge::new(source.src_id, source.src_port, id, 0));
+                }
+            };
+
+            for edge in added {
+                pending.extend(folding.positions.get(&edge.dest_id));
+                folding.add_edge(edge);
+            }
+        }
+
+        // Remove the Constants that are no longer connected to anything
+        let unused: Vec<_> = folding
+            .nodes
+            .iter()
+            .flatten()
+            .filter(|n| matches!(n.inner, NodeType::Constant { .. }))
+            .filter(|n| folding.outputs(n.id).is_empty())
+            .map(|n| n.id)
+            .collect();
+        for id in unused {
+            folding.remove_node(id);
+        }
+
+        self.nodes = folding.nodes.into_iter().flatten().collect();
+        self.edges = folding.edges.into_iter().flatten().collect();
+    }
+}
+
+/// The Nodes and Edges of a Graph while its Constants are folded, with the Edges of every Node
+/// looked up by its ID, so that folding a Node doesn't need to go through the whole Graph.
+/// Removed Nodes and Edges leave a Gap, so the Positions of the others stay the same
+struct Folding {
+    nodes: Vec<Option<Node>>,
+    edges: Vec<Option<Edge>>,
+    /// The Position of every Node by its ID
+    positions: HashMap<u32, usize>,
+    /// The Positions of the Edges directed at every Node
+    edges_to: HashMap<u32, Vec<usize>>,
+    /// The Positions of the Edges coming from every Node
+    edges_from: HashMap<u32, Vec<usize>>,
+    next_id: u32,
+}
+
+impl Folding {
+    fn new(nodes: Vec<Node>, edges: Vec<Edge>) -> Self {
+        let mut folding = Self {
+            next_id: nodes.iter().map(|n| n.id + 1).max().unwrap_or(0),
+            positions: nodes.iter().enumerate().map(|(i, n)| (n.id, i)).collect(),
+            nodes: nodes.into_iter().map(Some).collect(),
+            edges: Vec::new(),
+            edges_to: HashMap::new(),
+            edges_from: HashMap::new(),
+        };
+        for edge in edges {
+            folding.add_edge(edge);
+        }
+
+        folding
+    }
+
+    fn node(&self, id: u32) -> Option<&Node> {
+        self.nodes[*self.positions.get(&id)?].as_ref()
+    }
+
+    fn inputs(&self, id: u32) -> Vec<Edge> {
+        self.edges_at(self.edges_to.get(&id))
+    }
+
+    fn outputs(&self, id: u32) -> Vec<Edge> {
+        self.edges_at(self.edges_from.get(&id))
+    }
+
+    fn edges_at(&self, positions: Option<&Vec<usize>>) -> Vec<Edge> {
+        positions
+            .into_iter()
+            .flatten()
+            .filter_map(|p| self.edges[*p].clone())
+            .collect()
+    }
+
+    fn add_node(&mut self, inner: NodeType) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.positions.insert(id, self.nodes.len());
+        self.nodes.push(Some(Node::new(id, inner)));
+        id
+    }
+
+    fn add_edge(&mut self, edge: Edge) {
+        let position = self.edges.len();
+        self.edges_to
+            .entry(edge.dest_id)
+            .or_default()
+            .push(position);
+        self.edges_from
+            .entry(edge.src_id)
+            .or_default()
+            .push(position);
+        self.edges.push(Some(edge));
+    }
+
+    /// Removes the Node together with all of its Edges
+    fn remove_node(&mut self, id: u32) {
+        if let Some(position) = self.positions.remove(&id) {
+            self.nodes[position] = None;
+        }
+
+        for edge in self.inputs(id).iter().chain(self.outputs(id).iter()) {
+            self.remove_edge(edge);
+        }
+    }
+
+    fn remove_edge(&mut self, edge: &Edge) {
+        let position = self.edges_to.get(&edge.dest_id).and_then(|positions| {
+            positions
+                .iter()
+                .copied()
+                .find(|p| self.edges[*p].as_ref() == Some(edge))
+        });
+        let position = match position {
+            Some(p) => p,
+            None => return,
+        };
+
+        self.edges[position] = None;
+        if let Some(positions) = self.edges_to.get_mut(&edge.dest_id) {
+            positions.retain(|p| *p != position);
+        }
+        if let Some(positions) = self.edges_from.get_mut(&edge.src_id) {
+            positions.retain(|p| *p != position);
+        }
+    }
+
+    /// Returns the Value of the Constant driving the Edge, if its Source is a Constant
+    fn constant_source(&self, edge: &Edge) -> Option<bool> {
+        match self.node(edge.src_id)?.inner {
+            NodeType::Constant { value } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Determines how the Node can be simplified, based on its Constant Inputs
+    fn fold(&self, node: &Node) -> Option<Folded> {
+        let mut inputs = self.inputs(node.id);
+        inputs.sort_by_key(|e| e.dest_port);
+
+        let constants: Vec<_> = inputs.iter().map(|e| self.constant_source(e)).collect();
+        if constants.iter().all(|c| c.is_none()) {
+            return None;
+        }
+
+        match &node.inner {
+            // Outputs are part of the Interface and Constants already are as simple as possible
+            NodeType::Output { .. } | NodeType::Constant { .. } | NodeType::Clock { .. } => None,
+            NodeType::Input { .. } | NodeType::Variable { .. } | NodeType::Splitter { .. } => {
+                constants[0].map(Folded::Constant)
+            }
+            // The Value of Storage-Elements also depends on their previous State
+            NodeType::Operation {
+                op: BuiltinOp::Dff | BuiltinOp::Latch | BuiltinOp::RsLatch | BuiltinOp::Ram { .. },
+            } => None,
+            // The Words of a ROM are only known to its Layout
+            NodeType::Operation {
+                op: BuiltinOp::Rom { .. },
+            } => None,
+            // An Input that never changes also never triggers the Pulse
+            NodeType::Operation {
+                op: BuiltinOp::Pulse { .. },
+            } => Some(Folded::Constant(false)),
+            NodeType::Operation { op } => match (op, constants.as_slice()) {
+                (BuiltinOp::Not, [Some(value)]) => Some(Folded::Constant(!value)),
+                (op, [Some(first), Some(second)]) => Some(Folded::Constant(match op {
+                    BuiltinOp::And => first & second,
+                    BuiltinOp::Or => first | second,
+                    BuiltinOp::Xor => first ^ second,
+                    BuiltinOp::Nand => !(first & second),
+                    BuiltinOp::Nor => !(first | second),
+                    BuiltinOp::Xnor => !(first ^ second),
+                    BuiltinOp::Not => unreachable!("Not only has a single Input"),
+                    BuiltinOp::Dff
+                    | BuiltinOp::Latch
+                    | BuiltinOp::RsLatch
+                    | BuiltinOp::Ram { .. } => {
+                        unreachable!("Storage-Elements are never folded")
+                    }
+                    BuiltinOp::Pulse { .. } => unreachable!("Pulse only has a single Input"),
+                    BuiltinOp::Rom { .. } => unreachable!("ROMs are never folded"),
+                })),
+                (op, [first, second]) => {
+                    let (value, other) = match (first, second) {
+                        (Some(value), None) => (*value, inputs[1].clone()),
+                        (None, Some(value)) => (*value, inputs[0].clone()),
+                        _ => unreachable!("At least one of the Inputs is a Constant"),
+                    };
+
+                    Some(match (op, value) {
+                        (BuiltinOp::And, false) => Folded::Constant(false),
+                        (BuiltinOp::And, true) => Folded::Forward(other),
+                        (BuiltinOp::Or, false) => Folded::Forward(other),
+                        (BuiltinOp::Or, true) => Folded::Constant(true),
+                        (BuiltinOp::Xor, false) => Folded::Forward(other),
+                        (BuiltinOp::Xor, true) => Folded::Invert(other),
+                        (BuiltinOp::Nand, false) => Folded::Constant(true),
+                        (BuiltinOp::Nand, true) => Folded::Invert(other),
+                        (BuiltinOp::Nor, false) => Folded::Invert(other),
+                        (BuiltinOp::Nor, true) => Folded::Constant(false),
+                        (BuiltinOp::Xnor, false) => Folded::Invert(other),
+                        (BuiltinOp::Xnor, true) => Folded::Forward(other),
+                        (BuiltinOp::Not, _) => unreachable!("Not only has a single Input"),
+                        (
+                            BuiltinOp::Dff
+                            | BuiltinOp::Latch
+                            | BuiltinOp::RsLatch
+                            | BuiltinOp::Ram { .. },
+                            _,
+                        ) => {
+                            unreachable!("Storage-Elements are never folded")
+                        }
+                        (BuiltinOp::Pulse { .. }, _) => {
+                            unreachable!("Pulse only has a single Input")
+                        }
+                        (BuiltinOp::Rom { .. }, _) => unreachable!("ROMs are never folded"),
+                    })
+                }
+                _ => None,
+            },
+        }
+    }
+}
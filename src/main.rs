@@ -6,15 +6,38 @@ struct Arguments {
     file: String,
     #[clap(name = "target")]
     target: Option<String>,
+    /// The Architecture of the generated Adders
+    #[clap(long, arg_enum, default_value = "ripple")]
+    adder: Adder,
+}
+
+#[derive(Debug, Clone, clap::ArgEnum)]
+enum Adder {
+    Ripple,
+    Lookahead,
 }
 
 fn main() {
     let args = Arguments::parse();
     dbg!(&args);
 
-    let content = std::fs::read_to_string(args.file).unwrap();
+    let content = std::fs::read_to_string(&args.file).unwrap();
+    let source = mclc::frontend::Span::with_name(args.file, content);
+
+    let options = mclc::frontend::Options {
+        adder: match args.adder {
+            Adder::Ripple => mclc::frontend::AdderKind::Ripple,
+            Adder::Lookahead => mclc::frontend::AdderKind::Lookahead,
+        },
+    };
 
-    let mut graph = mclc::frontend::parse(content, args.target).unwrap();
+    let mut graph = match mclc::frontend::parse_with_options(source, args.target, &options) {
+        Ok(g) => g,
+        Err(e) => {
+            eprint!("{}", e);
+            std::process::exit(1);
+        }
+    };
     graph.optimize();
 
     println!("Generated Graph");
@@ -0,0 +1,116 @@
+mod common;
+
+use std::collections::HashMap;
+
+use mclc::frontend::{parse_with_options, AdderKind, Options};
+
+use common::{output, parse_err};
+
+/// The Values of the Bits of the 3-Bit Inputs `a` and `b`
+fn operands(a: u32, b: u32) -> HashMap<String, bool> {
+    (0..3)
+        .flat_map(|i| {
+            [
+                (format!("a[{}]", i), a & (1 << i) != 0),
+                (format!("b[{}]", i), b & (1 << i) != 0),
+            ]
+        })
+        .collect()
+}
+
+/// Checks the Expression on all Combinations of two 3-Bit Operands, with both Adder-Kinds
+fn check<F>(expression: &str, out_ty: &str, expected: F)
+where
+    F: Fn(u32, u32) -> u32,
+{
+    let content = format!(
+        "entity Test {{
+  in_ports {{
+    a : bits[3];
+    b : bits[3];
+  }}
+  out_ports {{
+    x : {};
+  }}
+  behaviour {{
+    (x) <= {};
+  }}
+}}",
+        out_ty, expression
+    );
+
+    for adder in [AdderKind::Ripple, AdderKind::Lookahead] {
+        let options = Options {
+            adder: adder.clone(),
+        };
+        let mut graph = parse_with_options(content.as_str(), None, &options).unwrap();
+        graph.optimize();
+
+        for a in 0..8 {
+            for b in 0..8 {
+                assert_eq!(
+                    expected(a, b),
+                    output(&graph, &operands(a, b)),
+                    "{} with a = {}, b = {} and a {:?} Adder",
+                    expression,
+                    a,
+                    b,
+                    adder
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn add() {
+    check("a + b", "bits[3]", |a, b| (a + b) % 8);
+}
+
+#[test]
+fn sub() {
+    check("a - b", "bits[3]", |a, b| (a + 8 - b) % 8);
+}
+
+#[test]
+fn equals() {
+    check("a == b", "bit", |a, b| (a == b) as u32);
+}
+
+#[test]
+fn less() {
+    check("a < b", "bit", |a, b| (a < b) as u32);
+}
+
+#[test]
+fn precedence() {
+    check("a - b < b & a + b == b", "bit", |a, b| {
+        (((a + 8 - b) % 8 < b) && (a + b) % 8 == b) as u32
+    });
+}
+
+#[test]
+fn width_mismatch() {
+    let content = "entity Test {
+  in_ports {
+    a : bits[3];
+    b : bits[2];
+  }
+  out_ports {
+    x : bits[3];
+  }
+  behaviour {
+    (x) <= a + b;
+  }
+}";
+
+    assert_eq!(
+        "error: all the arguments of `+` need to have the same width, expected width 3, found width 2
+  --> test.mcl:10:16
+   |
+10 |     (x) <= a + b;
+   |                ^
+",
+        parse_err(content)
+    );
+}
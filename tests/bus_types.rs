@@ -0,0 +1,234 @@
+mod common;
+
+use mclc::{
+    frontend::parse,
+    graph::normalized::{Graph, NodeType},
+};
+
+use common::parse_err;
+
+fn node_name(graph: &Graph, id: u32) -> &str {
+    match &graph.get_node(id).unwrap().inner {
+        NodeType::Input { name, .. } | NodeType::Output { name, .. } => name,
+        other => panic!("Expected an Input or Output, got {:?}", other),
+    }
+}
+
+#[test]
+fn slices_and_concat() {
+    let content = "entity Swap {
+  in_ports {
+    a : bits[4];
+  }
+  out_ports {
+    b : bits[4];
+  }
+  behaviour {
+    (b) <= concat(a[1:0], a[3:2]);
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    let mut connections: Vec<_> = graph
+        .edges
+        .iter()
+        .map(|e| (node_name(&graph, e.src_id), node_name(&graph, e.dest_id)))
+        .collect();
+    connections.sort();
+
+    assert_eq!(
+        vec![
+            ("a[0]", "b[2]"),
+            ("a[1]", "b[3]"),
+            ("a[2]", "b[0]"),
+            ("a[3]", "b[1]"),
+        ],
+        connections
+    );
+}
+
+#[test]
+fn bus_ports_on_entities() {
+    let content = "entity Top {
+  in_ports {
+    x : bits[2];
+    y : bits[2];
+  }
+  out_ports {
+    z : bits[2];
+  }
+  behaviour {
+    (z[0]) <= Bit(x[0], y[0]);
+    (z[1]) <= Bit(x[1], y[1]);
+  }
+}
+
+entity Bit {
+  in_ports {
+    a : bit;
+    b : bit;
+  }
+  out_ports {
+    c : bit;
+  }
+  behaviour {
+    (c) <= and(a, b);
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    let and_count = graph
+        .nodes
+        .iter()
+        .filter(|n| matches!(n.inner, NodeType::Operation { .. }))
+        .count();
+    assert_eq!(2, and_count);
+}
+
+#[test]
+fn index_out_of_range() {
+    let content = "entity Test {
+  in_ports {
+    a : bits[4];
+  }
+  out_ports {
+    b : bit;
+  }
+  behaviour {
+    (b) <= (a[4]);
+  }
+}";
+
+    assert_eq!(
+        "error: the selected bits are out of range for type `bits[4]`
+ --> test.mcl:9:13
+  |
+9 |     (b) <= (a[4]);
+  |             ^^^^
+",
+        parse_err(content)
+    );
+}
+
+#[test]
+fn width_mismatch() {
+    let content = "entity Test {
+  in_ports {
+    a : bits[4];
+  }
+  out_ports {
+    b : bits[2];
+  }
+  behaviour {
+    (b) <= (a);
+  }
+}";
+
+    assert_eq!(
+        "error: expected type `bits[2]`, found `bits[4]`
+ --> test.mcl:9:6
+  |
+9 |     (b) <= (a);
+  |      ^
+",
+        parse_err(content)
+    );
+}
+
+fn operation_count(graph: &Graph) -> usize {
+    graph
+        .nodes
+        .iter()
+        .filter(|n| matches!(n.inner, NodeType::Operation { .. }))
+        .count()
+}
+
+/// The longest Number of Operations between any Input and the given Node
+fn depth(graph: &Graph, id: u32) -> usize {
+    let own = match graph.get_node(id).unwrap().inner {
+        NodeType::Operation { .. } => 1,
+        _ => 0,
+    };
+
+    graph
+        .edges_to_node(id)
+        .iter()
+        .map(|e| depth(graph, e.src_id))
+        .max()
+        .unwrap_or(0)
+        + own
+}
+
+#[test]
+fn bitwise_ops_on_buses() {
+    let content = "entity Test {
+  in_ports {
+    a : bits[4];
+    b : bits[4];
+  }
+  out_ports {
+    c : bits[4];
+  }
+  behaviour {
+    (c) <= and(a, b);
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    assert_eq!(4, operation_count(&graph));
+}
+
+#[test]
+fn reduction_is_balanced() {
+    let content = "entity Test {
+  in_ports {
+    a : bits[8];
+  }
+  out_ports {
+    parity : bit;
+  }
+  behaviour {
+    (parity) <= xor_reduce(a);
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    assert_eq!(7, operation_count(&graph));
+
+    let output = graph
+        .nodes
+        .iter()
+        .find(|n| matches!(n.inner, NodeType::Output { .. }))
+        .unwrap();
+    assert_eq!(3, depth(&graph, output.id));
+}
+
+#[test]
+fn bitwise_width_mismatch() {
+    let content = "entity Test {
+  in_ports {
+    a : bits[4];
+    b : bits[2];
+  }
+  out_ports {
+    c : bits[4];
+  }
+  behaviour {
+    (c) <= or(a, b);
+  }
+}";
+
+    assert_eq!(
+        "error: all the arguments of `or` need to have the same width, expected width 4, found width 2
+  --> test.mcl:10:18
+   |
+10 |     (c) <= or(a, b);
+   |                  ^
+",
+        parse_err(content)
+    );
+}
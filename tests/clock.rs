@@ -0,0 +1,163 @@
+mod common;
+
+use mclc::{
+    backend::generate_layout,
+    frontend::parse,
+    graph::normalized::{BuiltinOp, NodeType},
+};
+
+use common::parse_err;
+
+fn entity(value: &str) -> String {
+    format!(
+        "entity Test {{
+  in_ports {{
+    a : bit;
+  }}
+  out_ports {{
+    x : bit;
+  }}
+  behaviour {{
+    (x) <= {};
+  }}
+}}",
+        value
+    )
+}
+
+#[test]
+fn clock_source() {
+    let graph = parse(entity("dff(a, clock(8))").as_str(), None).unwrap();
+
+    assert!(graph
+        .nodes
+        .iter()
+        .any(|n| n.inner == NodeType::Clock { period: 8 }));
+}
+
+#[test]
+fn pulse_per_bit() {
+    let content = "entity Test {
+  in_ports {
+    a : bits[2];
+  }
+  out_ports {
+    x : bits[2];
+  }
+  behaviour {
+    (x) <= pulse(a, 3);
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    let pulses = graph
+        .nodes
+        .iter()
+        .filter(|n| {
+            n.inner
+                == NodeType::Operation {
+                    op: BuiltinOp::Pulse { length: 3 },
+                }
+        })
+        .count();
+    assert_eq!(2, pulses);
+}
+
+#[test]
+fn generic_period() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+    y : bit;
+  }
+  behaviour {
+    (x, y) <= Ticker<6>(a);
+  }
+}
+
+entity Ticker<P: int> {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+    y : bit;
+  }
+  behaviour {
+    (x) <= dff(a, clock(P));
+    (y) <= pulse(a, P);
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    assert!(graph
+        .nodes
+        .iter()
+        .any(|n| n.inner == NodeType::Clock { period: 6 }));
+    assert!(graph.nodes.iter().any(|n| n.inner
+        == NodeType::Operation {
+            op: BuiltinOp::Pulse { length: 6 },
+        }));
+}
+
+#[test]
+fn invalid_period() {
+    for period in ["5", "2", "a"] {
+        let err = parse_err(entity(&format!("dff(a, clock({}))", period)).as_str());
+
+        assert!(
+            err.starts_with(&format!(
+                "error: the period of a clock needs to be an even number of at least 4 ticks, found `{}`",
+                period
+            )),
+            "{}",
+            err
+        );
+    }
+}
+
+#[test]
+fn invalid_pulse_length() {
+    let err = parse_err(entity("pulse(a, 0)").as_str());
+
+    assert_eq!(
+        "error: the length of a pulse needs to be a number of at least 1 tick, found `0`
+ --> test.mcl:9:21
+  |
+9 |     (x) <= pulse(a, 0);
+  |                     ^
+",
+        err
+    );
+}
+
+#[test]
+fn place_clock() {
+    let mut graph = parse(entity("dff(a, clock(12))").as_str(), None).unwrap();
+    graph.optimize();
+
+    let layout = generate_layout(graph);
+
+    let commands = layout.placement().place_commands();
+    assert!(commands
+        .iter()
+        .any(|c| c.contains("repeater[facing=east,delay=4]")));
+}
+
+#[test]
+fn place_pulse() {
+    let mut graph = parse(entity("pulse(a, 2)").as_str(), None).unwrap();
+    graph.optimize();
+
+    let layout = generate_layout(graph);
+
+    let commands = layout.placement().place_commands();
+    assert!(commands
+        .iter()
+        .any(|c| c.contains("repeater[facing=east,delay=3]")));
+}
@@ -0,0 +1,91 @@
+#[test]
+fn comments_are_ignored() {
+    let content = "
+// A simple Gate
+entity AndGate { // trailing comment
+  in_ports {
+    a : bit; /* inline */
+    /* multi
+       line /* nested */ still in comment
+    */
+    b : bit;
+  }
+
+  out_ports {
+    c : bit;
+  }
+
+  behaviour {
+    // the only statement
+    (c) <= and(a, b);
+  }
+}
+";
+
+    let result = mclc::frontend::parse(content, None);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn unterminated_block_comment() {
+    let content = "entity Test { /* /* */ }";
+
+    let result = mclc::frontend::parse(content, None);
+
+    let err = result.unwrap_err();
+    let diagnostics = err.diagnostics();
+    assert_eq!(1, diagnostics.len());
+    assert_eq!("unterminated block comment", diagnostics[0].message());
+}
+
+#[test]
+fn doc_comments() {
+    let content = "
+/// Adds two Bits
+/// with a Carry
+entity HalfAdder {
+  in_ports {
+    /// The first Summand
+    a : bit;
+    b : bit;
+  }
+
+  out_ports {
+    //// Not a Doc-Comment
+    sum : bit;
+    /// Set on Overflow
+    carry : bit;
+  }
+
+  behaviour {
+    /// Ignored
+    (sum) <= xor(a, b);
+    (carry) <= and(a, b);
+  }
+}
+";
+
+    let docs = mclc::frontend::documentation(content).unwrap();
+
+    assert_eq!(1, docs.len());
+    let entity = &docs[0];
+    assert_eq!("HalfAdder", entity.name);
+    assert_eq!(Some("Adds two Bits\nwith a Carry"), entity.doc.as_deref());
+    assert_eq!(
+        vec![
+            ("a".to_string(), Some("The first Summand".to_string())),
+            ("b".to_string(), None)
+        ],
+        entity.in_ports
+    );
+    assert_eq!(
+        vec![
+            ("sum".to_string(), None),
+            ("carry".to_string(), Some("Set on Overflow".to_string()))
+        ],
+        entity.out_ports
+    );
+
+    assert!(mclc::frontend::parse(content, None).is_ok());
+}
@@ -0,0 +1,105 @@
+//! Helpers shared by the Integration-Tests, where every Test only uses some of them
+#![allow(dead_code)]
+
+use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+
+use mclc::{
+    frontend::{parse, Span},
+    graph::normalized::{BuiltinOp, Graph, NodeType},
+};
+
+/// Parses the Content as the Source `test.mcl` and returns the Error it fails with
+pub fn parse_err(content: &str) -> String {
+    parse_source_err(Span::with_name("test.mcl", content))
+}
+
+/// Parses the Source and returns the Error it fails with
+pub fn parse_source_err(source: Span) -> String {
+    match parse(source, None) {
+        Ok(_) => panic!("Expected the Parsing to fail"),
+        Err(e) => e.to_string(),
+    }
+}
+
+/// Computes the Value of the Node for the given Values of the Input-Bits
+pub fn evaluate<K>(graph: &Graph, id: u32, inputs: &HashMap<K, bool>) -> bool
+where
+    K: Borrow<str> + Hash + Eq,
+{
+    let node = graph.get_node(id).unwrap();
+    let mut edges = graph.edges_to_node(id);
+    edges.sort_by_key(|e| e.dest_port);
+    let args: Vec<_> = edges
+        .iter()
+        .map(|e| evaluate(graph, e.src_id, inputs))
+        .collect();
+
+    match &node.inner {
+        NodeType::Input { name, .. } if args.is_empty() => inputs[name.as_str()],
+        NodeType::Constant { value } => *value,
+        NodeType::Operation { op } => match op {
+            BuiltinOp::And => args[0] & args[1],
+            BuiltinOp::Or => args[0] | args[1],
+            BuiltinOp::Xor => args[0] ^ args[1],
+            BuiltinOp::Nand => !(args[0] & args[1]),
+            BuiltinOp::Nor => !(args[0] | args[1]),
+            BuiltinOp::Xnor => !(args[0] ^ args[1]),
+            BuiltinOp::Not => !args[0],
+            other => panic!("Unexpected Operation {:?}", other),
+        },
+        _ => args[0],
+    }
+}
+
+/// Computes the Values of the Output-Bits, whose Names start with the Prefix, sorted by their
+/// Number
+fn port_outputs<K>(graph: &Graph, prefix: &str, inputs: &HashMap<K, bool>) -> Vec<bool>
+where
+    K: Borrow<str> + Hash + Eq,
+{
+    let mut outputs: Vec<_> = graph
+        .nodes
+        .iter()
+        .filter_map(|n| match &n.inner {
+            NodeType::Output { name, number } if name.starts_with(prefix) => Some((*number, n.id)),
+            _ => None,
+        })
+        .collect();
+    outputs.sort();
+
+    outputs
+        .into_iter()
+        .map(|(_, id)| evaluate(graph, id, inputs))
+        .collect()
+}
+
+/// Computes the Values of all the Output-Bits, sorted by their Number
+pub fn outputs<K>(graph: &Graph, inputs: &HashMap<K, bool>) -> Vec<bool>
+where
+    K: Borrow<str> + Hash + Eq,
+{
+    port_outputs(graph, "", inputs)
+}
+
+/// Computes the Value of all the Output-Bits, as a Number with the first Bit as the lowest
+pub fn output<K>(graph: &Graph, inputs: &HashMap<K, bool>) -> u32
+where
+    K: Borrow<str> + Hash + Eq,
+{
+    to_number(outputs(graph, inputs))
+}
+
+/// Computes the Value of the Output-Bits of the Port, as a Number with the first Bit as the lowest
+pub fn port_output<K>(graph: &Graph, port: &str, inputs: &HashMap<K, bool>) -> u32
+where
+    K: Borrow<str> + Hash + Eq,
+{
+    to_number(port_outputs(graph, port, inputs))
+}
+
+fn to_number(bits: Vec<bool>) -> u32 {
+    bits.into_iter()
+        .enumerate()
+        .map(|(i, bit)| (bit as u32) << i)
+        .sum()
+}
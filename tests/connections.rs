@@ -0,0 +1,173 @@
+mod common;
+
+use mclc::{
+    frontend::parse,
+    graph::normalized::{Graph, NodeType},
+};
+
+use common::parse_err;
+
+/// Returns the Name of the Input that is connected to the Output, through any Number of inlined
+/// Entities
+fn source(graph: &Graph, output: &str) -> String {
+    let mut id = graph
+        .nodes
+        .iter()
+        .find(|n| matches!(&n.inner, NodeType::Output { name, .. } if name == output))
+        .unwrap()
+        .id;
+
+    loop {
+        match graph.edges_to_node(id).as_slice() {
+            [] => match &graph.get_node(id).unwrap().inner {
+                NodeType::Input { name, .. } => return name.clone(),
+                other => panic!("Unexpected Node {:?}", other),
+            },
+            [edge] => id = edge.src_id,
+            other => panic!("Expected a single Edge, found {:?}", other),
+        };
+    }
+}
+
+fn entity(behaviour: &str) -> String {
+    format!(
+        "entity Test {{
+  in_ports {{
+    p : bit;
+    q : bit;
+  }}
+  out_ports {{
+    x : bit;
+    y : bit;
+  }}
+  behaviour {{
+    {}
+  }}
+}}
+
+entity Swap {{
+  in_ports {{
+    first : bit;
+    second : bit;
+  }}
+  out_ports {{
+    low : bit;
+    high : bit;
+  }}
+  behaviour {{
+    (low, high) <= (second, first);
+  }}
+}}",
+        behaviour
+    )
+}
+
+#[test]
+fn named_arguments() {
+    let graph = parse(
+        entity("(x, y) <= Swap(second: q, first: p);").as_str(),
+        None,
+    )
+    .unwrap();
+    assert_eq!("q", source(&graph, "x"));
+    assert_eq!("p", source(&graph, "y"));
+
+    let graph = parse(entity("(x, y) <= Swap(q, second: p);").as_str(), None).unwrap();
+    assert_eq!("p", source(&graph, "x"));
+    assert_eq!("q", source(&graph, "y"));
+}
+
+#[test]
+fn named_targets() {
+    let graph = parse(
+        entity("(.high: h, .low: l) = Swap(p, q); (x, y) <= (h, l);").as_str(),
+        None,
+    )
+    .unwrap();
+    assert_eq!("p", source(&graph, "x"));
+    assert_eq!("q", source(&graph, "y"));
+}
+
+#[test]
+fn missing_input() {
+    assert_eq!(
+        "error: the input port `second` of `Swap` is not connected
+  --> test.mcl:11:15
+   |
+11 |     (x, y) <= Swap(first: p);
+   |               ^^^^^^^^^^^^^^
+",
+        parse_err(&entity("(x, y) <= Swap(first: p);"))
+    );
+}
+
+#[test]
+fn duplicate_output() {
+    assert_eq!(
+        "error: the output port `low` is connected multiple times
+  --> test.mcl:11:16
+   |
+11 |     (.low: x, .low: y) <= Swap(p, q);
+   |                ^^^
+
+error: the first connection is here
+  --> test.mcl:11:7
+   |
+11 |     (.low: x, .low: y) <= Swap(p, q);
+   |       ^^^^^^
+",
+        parse_err(&entity("(.low: x, .low: y) <= Swap(p, q);"))
+    );
+}
+
+#[test]
+fn unknown_port() {
+    assert_eq!(
+        "error: `third` is not an input port of `Swap`
+  --> test.mcl:11:30
+   |
+11 |     (x, y) <= Swap(first: p, third: q);
+   |                              ^^^^^
+",
+        parse_err(&entity("(x, y) <= Swap(first: p, third: q);"))
+    );
+}
+
+#[test]
+fn positional_after_named() {
+    assert_eq!(
+        "error: a connection by position can't follow a connection by name
+  --> test.mcl:11:31
+   |
+11 |     (x, y) <= Swap(second: p, q);
+   |                               ^
+",
+        parse_err(&entity("(x, y) <= Swap(second: p, q);"))
+    );
+}
+
+#[test]
+fn named_builtin_argument() {
+    assert_eq!(
+        "error: `and` is not an entity, so its arguments can't be named
+  --> test.mcl:11:20
+   |
+11 |     (x, y) <= (and(a: p, q), q);
+   |                    ^
+",
+        parse_err(&entity("(x, y) <= (and(a: p, q), q);"))
+    );
+}
+
+#[test]
+fn named_target_without_entity() {
+    assert_eq!(
+        "error: only the results of an entity can be bound by name
+  --> test.mcl:11:7
+   |
+11 |     (.low: x, y) <= (p, q);
+   |       ^^^
+",
+        parse_err(&entity("(.low: x, y) <= (p, q);"))
+    );
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use mclc::{
+    frontend::parse,
+    graph::normalized::{BuiltinOp, Graph, NodeType},
+};
+
+use common::parse_err;
+
+fn optimized(content: &str) -> Graph {
+    let mut graph = parse(content, None).unwrap();
+    graph.optimize();
+    graph
+}
+
+/// The Values of the Constants driving the Outputs, ordered by the Number of the Output
+fn output_constants(graph: &Graph) -> Vec<Option<bool>> {
+    let mut outputs: Vec<_> = graph
+        .nodes
+        .iter()
+        .filter_map(|n| match n.inner {
+            NodeType::Output { number, .. } => Some((number, n.id)),
+            _ => None,
+        })
+        .collect();
+    outputs.sort();
+
+    outputs
+        .into_iter()
+        .map(|(_, id)| {
+            let edge = graph.edges_to_node(id).pop()?;
+            match graph.get_node(edge.src_id)?.inner {
+                NodeType::Constant { value } => Some(value),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn sized_constant() {
+    let content = "entity Test {
+  in_ports {
+  }
+  out_ports {
+    x : bits[8];
+  }
+  behaviour {
+    (x) <= 8'b1010_0001;
+  }
+}";
+
+    let graph = optimized(content);
+
+    assert_eq!(
+        vec![
+            Some(true),
+            Some(false),
+            Some(false),
+            Some(false),
+            Some(false),
+            Some(true),
+            Some(false),
+            Some(true)
+        ],
+        output_constants(&graph)
+    );
+}
+
+#[test]
+fn folds_operations() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    zero : bit;
+    same : bit;
+    inverted : bit;
+    one : bit;
+  }
+  behaviour {
+    (zero) <= and(a, 0);
+    (same) <= or(0, a);
+    (inverted) <= xor(a, 1);
+    (one) <= (1 | a) & or(a, 1);
+  }
+}";
+
+    let graph = optimized(content);
+
+    assert_eq!(
+        vec![Some(false), None, None, Some(true)],
+        output_constants(&graph)
+    );
+
+    let ops: Vec<_> = graph
+        .nodes
+        .iter()
+        .filter_map(|n| match &n.inner {
+            NodeType::Operation { op } => Some(op.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(vec![BuiltinOp::Not], ops);
+}
+
+#[test]
+fn constant_too_wide() {
+    let content = "entity Test {
+  in_ports {
+  }
+  out_ports {
+    x : bits[4];
+  }
+  behaviour {
+    (x) <= 4'hA1;
+  }
+}";
+
+    assert_eq!(
+        "error: the constant `4'hA1` does not fit into 4 bits
+ --> test.mcl:8:12
+  |
+8 |     (x) <= 4'hA1;
+  |            ^^^^^
+",
+        parse_err(content)
+    );
+}
@@ -0,0 +1,450 @@
+mod common;
+
+use mclc::frontend::{parse, Span};
+
+use common::parse_err;
+
+#[test]
+fn missing_semicolon() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    b : bit;
+  }
+  behaviour {
+    (b) <= not(a)
+  }
+}";
+
+    let err = parse_err(content);
+
+    assert_eq!(
+        "error: expected `;`, found the end of the block
+  --> test.mcl:10:3
+   |
+10 |   }
+   |   ^
+",
+        err
+    );
+}
+
+#[test]
+fn unknown_operand() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    b : bit;
+  }
+  behaviour {
+    (b) <= and(a, c);
+  }
+}";
+
+    let err = parse_err(content);
+
+    assert_eq!(
+        "error: `c` is neither an input port nor a previously assigned variable
+ --> test.mcl:9:19
+  |
+9 |     (b) <= and(a, c);
+  |                   ^
+",
+        err
+    );
+}
+
+#[test]
+fn unknown_type() {
+    let content = "entity Test {
+  in_ports {
+    a : byte;
+  }
+}";
+
+    let err = parse_err(content);
+
+    assert_eq!(
+        "error: unknown type `byte`
+ --> test.mcl:3:9
+  |
+3 |     a : byte;
+  |         ^^^^
+",
+        err
+    );
+}
+
+#[test]
+fn unknown_target() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+  }
+}";
+
+    let result = parse(content, Some("Other".to_string()));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn reports_all_syntax_errors() {
+    let content = "entity First {
+  in_ports {
+    a : bit
+    b : bit;
+  }
+  out_ports {
+    c : bit;
+  }
+  behaviour {
+    (tmp) = and(a b);
+    (c) <= not(tmp);
+    (c) <= ;
+  }
+}
+
+entity {
+}
+
+entity Second {
+  inports {
+    a : bit;
+  }
+  out_ports {
+    b : bit;
+  }
+}";
+
+    let err = parse(Span::with_name("test.mcl", content), None).unwrap_err();
+
+    let messages: Vec<_> = err
+        .diagnostics()
+        .iter()
+        .map(|d| {
+            let (line, _) = d.span().position(d.span().start());
+            (line, d.message().to_string())
+        })
+        .collect();
+
+    assert_eq!(
+        vec![
+            (4, "expected `;`, found `b`".to_string()),
+            (10, "expected one of `,` or `)`, found `b`".to_string()),
+            (
+                12,
+                "expected one of a name, `(` or `!`, found `;`".to_string()
+            ),
+            (16, "expected a name, found `{`".to_string()),
+            (
+                20,
+                "expected one of `in_ports`, `out_ports` or `behaviour`, found `inports`"
+                    .to_string()
+            ),
+        ],
+        messages
+    );
+}
+
+#[test]
+fn column_after_non_ascii_and_tabs() {
+    let content = "entity Test {\r\n\tin_ports {\r\n\t\tgröße : bit;\r\n\t}\r\n\tout_ports {\r\n\t\tb : bit;\r\n\t}\r\n\tbehaviour {\r\n\t\t(b) <= and(größe, ü);\r\n\t}\r\n}\r\n";
+
+    let err = parse_err(content);
+
+    assert_eq!(
+        "error: `ü` is neither an input port nor a previously assigned variable
+ --> test.mcl:9:21
+  |
+9 | \t\t(b) <= and(größe, ü);
+  | \t\t                  ^
+",
+        err
+    );
+}
+
+#[test]
+fn combinational_loop() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (b) = c;
+    (c) = b;
+    (x) <= a ^ c;
+  }
+}";
+
+    let err = parse_err(content);
+
+    assert_eq!(
+        "error: the variables `b` and `c` form a loop without any logic in between
+ --> test.mcl:9:5
+  |
+9 |     (b) = c;
+  |     ^^^^^^^^
+
+error: the loop of `b` and `c` continues here
+  --> test.mcl:10:5
+   |
+10 |     (c) = b;
+   |     ^^^^^^^^
+",
+        err
+    );
+}
+
+#[test]
+fn loop_through_gate() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (b) = c;
+    (c) = nor(a, b);
+    (x) <= c;
+  }
+}";
+
+    assert_eq!(
+        "error: the variables `b` and `c` form a loop through logic without any storage in between
+ --> test.mcl:9:5
+  |
+9 |     (b) = c;
+  |     ^^^^^^^^
+
+error: the loop of `b` and `c` continues here
+  --> test.mcl:10:5
+   |
+10 |     (c) = nor(a, b);
+   |     ^^^^^^^^^^^^^^^^
+",
+        parse_err(content)
+    );
+}
+
+#[test]
+fn loop_through_entity() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (b) = Pass(c);
+    (c) = and(a, b);
+    (x) <= c;
+  }
+}
+
+entity Pass {
+  in_ports {
+    i : bit;
+  }
+  out_ports {
+    o : bit;
+  }
+  behaviour {
+    (o) <= i;
+  }
+}";
+
+    assert_eq!(
+        "error: the signals `b`, `c`, `i` and `o` form a loop across entities without any storage in between\n",
+        parse_err(content)
+    );
+}
+
+#[test]
+fn loop_through_entity_with_storage() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+    clk : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (b) = Hold(c, clk);
+    (c) = and(a, b);
+    (x) <= c;
+  }
+}
+
+entity Hold {
+  in_ports {
+    i : bit;
+    clk : bit;
+  }
+  out_ports {
+    o : bit;
+  }
+  behaviour {
+    (o) <= dff(i, clk);
+  }
+}";
+
+    assert!(parse(content, None).is_ok());
+}
+
+#[test]
+fn recursive_entity() {
+    let content = "entity A {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= B(a);
+  }
+}
+
+entity B {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (y) = !a;
+    (x) <= A(y);
+  }
+}";
+
+    let err = parse_err(content);
+
+    assert_eq!(
+        "error: the entity `A` uses itself through `A` -> `B` -> `A`
+ --> test.mcl:9:5
+  |
+9 |     (x) <= B(a);
+  |     ^^^^^^^^^^^^
+
+error: `B` uses `A` here
+  --> test.mcl:22:5
+   |
+22 |     (x) <= A(y);
+   |     ^^^^^^^^^^^^
+",
+        err
+    );
+}
+
+#[test]
+fn entity_uses_itself() {
+    let content = "entity A {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= and(a, A(a));
+  }
+}";
+
+    let err = parse_err(content);
+
+    assert!(err.starts_with("error: the entity `A` uses itself through `A` -> `A`"));
+}
+
+#[test]
+fn reports_all_loops() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (b) = c;
+    (c) = b;
+    (d) = e;
+    (e) = d;
+    (x) <= b ^ d;
+  }
+}";
+
+    let err = parse_err(content);
+
+    assert!(err.contains("error: the variables `b` and `c` form a loop"));
+    assert!(err.contains("error: the variables `d` and `e` form a loop"));
+}
+
+#[test]
+fn reports_all_recursive_entities() {
+    let content = "entity A {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= and(B(a), C(a));
+  }
+}
+
+entity B {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= and(a, B(a));
+  }
+}
+
+entity C {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= D(a);
+  }
+}
+
+entity D {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= C(a);
+  }
+}";
+
+    let err = parse_err(content);
+
+    assert_eq!(2, err.matches("uses itself through").count());
+    assert!(err.contains("error: the entity `B` uses itself through `B` -> `B`"));
+    assert!(err.contains("error: the entity `C` uses itself through `C` -> `D` -> `C`"));
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use mclc::{
+    frontend::parse,
+    graph::normalized::{BuiltinOp, Graph, NodeType},
+};
+
+use common::parse_err;
+
+fn ops(graph: &Graph) -> Vec<BuiltinOp> {
+    graph
+        .nodes
+        .iter()
+        .filter_map(|n| match &n.inner {
+            NodeType::Operation { op } => Some(op.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn entity(behaviour: &str) -> String {
+    format!(
+        "entity Test {{
+  in_ports {{
+    p : bit;
+    q : bit;
+  }}
+  out_ports {{
+    x : bit;
+  }}
+  behaviour {{
+    {}
+  }}
+}}
+
+entity Half {{
+  in_ports {{
+    a : bit;
+    b : bit;
+  }}
+  out_ports {{
+    sum : bit;
+    carry : bit;
+  }}
+  behaviour {{
+    (sum) <= xor(a, b);
+    (carry) <= and(a, b);
+  }}
+}}",
+        behaviour
+    )
+}
+
+#[test]
+fn discarded_port() {
+    let graph = parse(entity("(x, _) <= Half(p, q);").as_str(), None).unwrap();
+    assert_eq!(vec![BuiltinOp::Xor], ops(&graph));
+
+    let graph = parse(entity("(_, x) <= Half(p, q);").as_str(), None).unwrap();
+    assert_eq!(vec![BuiltinOp::And], ops(&graph));
+}
+
+#[test]
+fn discarded_variable() {
+    let graph = parse(entity("(_, c) = Half(p, q); (x) <= not(c);").as_str(), None).unwrap();
+    assert_eq!(vec![BuiltinOp::Not, BuiltinOp::And], ops(&graph));
+}
+
+#[test]
+fn discarded_named_target() {
+    let graph = parse(
+        entity("(.carry: _, .sum: s) = Half(p, q); (x) <= (s);").as_str(),
+        None,
+    )
+    .unwrap();
+    assert_eq!(vec![BuiltinOp::Xor], ops(&graph));
+}
+
+#[test]
+fn discard_count() {
+    assert_eq!(
+        "error: the value produces 2 results, but 3 targets were given
+  --> test.mcl:10:5
+   |
+10 |     (x, _, _) <= Half(p, q);
+   |     ^^^^^^^^^^^^^^^^^^^^^^^^
+",
+        parse_err(&entity("(x, _, _) <= Half(p, q);"))
+    );
+}
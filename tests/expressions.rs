@@ -0,0 +1,214 @@
+mod common;
+
+use mclc::{
+    frontend::parse,
+    graph::normalized::{BuiltinOp, Graph, NodeType},
+};
+
+use common::parse_err;
+
+/// Renders the Expression computed for the given Node, like `((a & b) | c)`
+fn render(graph: &Graph, id: u32) -> String {
+    let node = graph.get_node(id).unwrap();
+    let mut edges = graph.edges_to_node(id);
+    edges.sort_by_key(|e| e.dest_port);
+    let args: Vec<_> = edges.iter().map(|e| render(graph, e.src_id)).collect();
+
+    match &node.inner {
+        // The Inputs of inlined Entities are connected to their Arguments
+        NodeType::Input { name, .. } if args.is_empty() => name.clone(),
+        NodeType::Constant { value } => (*value as u8).to_string(),
+        NodeType::Clock { period } => format!("clock({})", period),
+        NodeType::Input { .. }
+        | NodeType::Output { .. }
+        | NodeType::Variable { .. }
+        | NodeType::Splitter { .. } => args[0].clone(),
+        NodeType::Operation { op } => {
+            let op = match op {
+                BuiltinOp::And => "&",
+                BuiltinOp::Or => "|",
+                BuiltinOp::Xor => "^",
+                BuiltinOp::Not => "!",
+                BuiltinOp::Nand => "nand",
+                BuiltinOp::Nor => "nor",
+                BuiltinOp::Xnor => "xnor",
+                BuiltinOp::Dff => "dff",
+                BuiltinOp::Latch => "latch",
+                BuiltinOp::RsLatch => "rs_latch",
+                BuiltinOp::Pulse { .. } => "pulse",
+                BuiltinOp::Rom { .. } => "rom",
+                BuiltinOp::Ram { .. } => "ram",
+            };
+            format!("({})", args.join(&format!(" {} ", op)))
+        }
+    }
+}
+
+fn rendered_output(content: &str) -> String {
+    let graph = parse(content, None).unwrap();
+
+    let output = graph
+        .nodes
+        .iter()
+        .find(|n| matches!(n.inner, NodeType::Output { .. }))
+        .unwrap();
+    render(&graph, output.id)
+}
+
+#[test]
+fn precedence() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+    b : bit;
+    c : bit;
+    d : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= a | b ^ c & d;
+  }
+}";
+
+    assert_eq!("(a | (b ^ (c & d)))", rendered_output(content));
+}
+
+#[test]
+fn parentheses() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+    b : bit;
+    c : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (tmp) = (a | b) & c;
+    (x) <= (tmp ^ a);
+  }
+}";
+
+    assert_eq!("(((a | b) & c) ^ a)", rendered_output(content));
+}
+
+#[test]
+fn operator_width_mismatch() {
+    let content = "entity Test {
+  in_ports {
+    a : bits[2];
+    b : bit;
+  }
+  out_ports {
+    x : bits[2];
+  }
+  behaviour {
+    (x) <= (a & b);
+  }
+}";
+
+    assert_eq!(
+        "error: all the arguments of `&` need to have the same width, expected width 2, found width 1
+  --> test.mcl:10:17
+   |
+10 |     (x) <= (a & b);
+   |                 ^
+",
+        parse_err(content)
+    );
+}
+
+#[test]
+fn nested_calls() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+    b : bit;
+    x : bit;
+    y : bit;
+  }
+  out_ports {
+    c : bit;
+  }
+  behaviour {
+    (c) <= or(and(a, b), and(x, y));
+  }
+}";
+
+    assert_eq!("((a & b) | (x & y))", rendered_output(content));
+}
+
+#[test]
+fn nested_entity_call() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+    b : bit;
+    c : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= Both(Both(a, b), c) | a;
+  }
+}
+
+entity Both {
+  in_ports {
+    first : bit;
+    second : bit;
+  }
+  out_ports {
+    res : bit;
+  }
+  behaviour {
+    (res) <= and(first, second);
+  }
+}";
+
+    assert_eq!("(((a & b) & c) | a)", rendered_output(content));
+}
+
+#[test]
+fn nested_call_with_multiple_results() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+    b : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= not(Split(a, b));
+  }
+}
+
+entity Split {
+  in_ports {
+    first : bit;
+    second : bit;
+  }
+  out_ports {
+    low : bit;
+    high : bit;
+  }
+  behaviour {
+    (low, high) <= (first, second);
+  }
+}";
+
+    assert_eq!(
+        "error: the call produces 2 results, but only a single result can be used here
+  --> test.mcl:10:16
+   |
+10 |     (x) <= not(Split(a, b));
+   |                ^^^^^^^^^^^
+",
+        parse_err(content)
+    );
+}
@@ -0,0 +1,109 @@
+use mclc::{
+    backend::generate_layout,
+    frontend::parse,
+    graph::normalized::{BuiltinOp, NodeType},
+};
+
+const TOGGLE: &str = "entity Toggle {
+  in_ports {
+    clk : bit;
+  }
+  out_ports {
+    q : bit;
+  }
+  behaviour {
+    (state) = dff(!state, clk);
+    (q) <= state;
+  }
+}";
+
+#[test]
+fn loop_is_cut_at_storage() {
+    let mut graph = parse(TOGGLE, None).unwrap();
+    graph.optimize();
+
+    let feedback = graph.feedback_edges();
+    assert_eq!(1, feedback.len());
+    let edge = feedback.iter().next().unwrap();
+    assert!(matches!(
+        graph.get_node(edge.src_id).unwrap().inner,
+        NodeType::Operation { op: BuiltinOp::Dff }
+    ));
+
+    let layout = generate_layout(graph);
+    assert!(!layout.placement().place_commands().is_empty());
+}
+
+#[test]
+fn bus_loop() {
+    let content = "entity Shift {
+  in_ports {
+    d : bits[4];
+    clk : bit;
+  }
+  out_ports {
+    q : bits[4];
+  }
+  behaviour {
+    (state) = dff(concat(state[2:0], state[3]) ^ d, clk);
+    (q) <= state;
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    let dffs = graph
+        .nodes
+        .iter()
+        .filter(|n| matches!(n.inner, NodeType::Operation { op: BuiltinOp::Dff }))
+        .count();
+    assert_eq!(4, dffs);
+    assert_eq!(1, graph.feedback_edges().len());
+}
+
+#[test]
+fn acyclic_graph() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+    b : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= a ^ b;
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    assert!(graph.feedback_edges().is_empty());
+}
+
+#[test]
+fn loop_of_different_widths() {
+    let content = "entity Rotate {
+  in_ports {
+    d : bits[4];
+    clk : bit;
+  }
+  out_ports {
+    q : bits[4];
+  }
+  behaviour {
+    (low) = state[0];
+    (state) = dff(concat(low, state[3:1]) ^ d, clk);
+    (q) <= state;
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    let dffs = graph
+        .nodes
+        .iter()
+        .filter(|n| matches!(n.inner, NodeType::Operation { op: BuiltinOp::Dff }))
+        .count();
+    assert_eq!(4, dffs);
+}
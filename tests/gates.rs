@@ -0,0 +1,49 @@
+use mclc::{
+    backend::generate_layout,
+    frontend::parse,
+    graph::normalized::{BuiltinOp, NodeType},
+};
+
+const CONTENT: &str = "entity Gates {
+  in_ports {
+    a : bit;
+    b : bit;
+  }
+  out_ports {
+    x : bit;
+    y : bit;
+    z : bit;
+  }
+  behaviour {
+    (x) <= nand(a, b);
+    (y) <= nor(a, b);
+    (z) <= xnor(a, b);
+  }
+}";
+
+#[test]
+fn inverted_gates() {
+    let graph = parse(CONTENT, None).unwrap();
+
+    let ops: Vec<_> = graph
+        .nodes
+        .iter()
+        .filter_map(|n| match &n.inner {
+            NodeType::Operation { op } => Some(op.clone()),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(vec![BuiltinOp::Nand, BuiltinOp::Nor, BuiltinOp::Xnor], ops);
+}
+
+#[test]
+fn place_inverted_gates() {
+    let mut graph = parse(CONTENT, None).unwrap();
+    graph.optimize();
+
+    let layout = generate_layout(graph);
+
+    let commands = layout.placement().place_commands();
+    assert!(commands.iter().any(|c| c.contains("redstone_wall_torch")));
+}
@@ -0,0 +1,194 @@
+mod common;
+
+use std::collections::HashMap;
+
+use mclc::frontend::parse;
+
+use common::{output, parse_err};
+
+/// The Values of the Bits of the Inputs `a` and `b` with the given Width
+fn operands(width: u32, a: u32, b: u32) -> HashMap<String, bool> {
+    (0..width)
+        .flat_map(|i| {
+            [
+                (format!("a[{}]", i), a & (1 << i) != 0),
+                (format!("b[{}]", i), b & (1 << i) != 0),
+            ]
+        })
+        .collect()
+}
+
+/// A Ripple-Carry-Adder, whose Carry-Out is the highest Bit of the Sum
+const RIPPLE_ADDER: &str = "
+entity Ripple<N: int> {
+  in_ports {
+    a : bits[N];
+    b : bits[N];
+  }
+  out_ports {
+    sum : bits[N];
+    carry_out : bit;
+  }
+  behaviour {
+    (carry[0]) = 0;
+    for i in 0..N {
+      (bit_sum[i], carry[i + 1]) = FullAdder(a[i], b[i], carry[i]);
+      (sum[i]) <= bit_sum[i];
+    }
+    (carry_out) <= carry[N];
+  }
+}
+
+entity FullAdder {
+  in_ports {
+    x : bit;
+    y : bit;
+    c : bit;
+  }
+  out_ports {
+    s : bit;
+    c_out : bit;
+  }
+  behaviour {
+    (s) <= x ^ y ^ c;
+    (c_out) <= x & y | c & (x ^ y);
+  }
+}";
+
+fn check_ripple(width: u32) {
+    let content = format!(
+        "entity Test {{
+  in_ports {{
+    a : bits[{0}];
+    b : bits[{0}];
+  }}
+  out_ports {{
+    x : bits[{0}];
+    carry : bit;
+  }}
+  behaviour {{
+    (x, carry) <= Ripple<{0}>(a, b);
+  }}
+}}
+{1}",
+        width, RIPPLE_ADDER
+    );
+
+    let mut graph = parse(content.as_str(), None).unwrap();
+    graph.optimize();
+
+    for a in 0..1 << width {
+        for b in 0..1 << width {
+            assert_eq!(a + b, output(&graph, &operands(width, a, b)));
+        }
+    }
+}
+
+#[test]
+fn ripple_carry() {
+    check_ripple(2);
+    check_ripple(3);
+}
+
+#[test]
+fn conditional() {
+    let entity = |width: u32| {
+        format!(
+            "entity Test {{
+  in_ports {{
+    a : bits[{0}];
+    b : bits[{0}];
+  }}
+  out_ports {{
+    x : bit;
+  }}
+  behaviour {{
+    (x) <= Select<{0}>(a, b);
+  }}
+}}
+
+entity Select<N: int> {{
+  in_ports {{
+    a : bits[N];
+    b : bits[N];
+  }}
+  out_ports {{
+    x : bit;
+  }}
+  behaviour {{
+    if N > 2 {{
+      (x) <= a[N - 1];
+    }} else {{
+      (x) <= b[0];
+    }}
+  }}
+}}",
+            width
+        )
+    };
+
+    let mut graph = parse(entity(3).as_str(), None).unwrap();
+    graph.optimize();
+    assert_eq!(1, output(&graph, &operands(3, 0b100, 0)));
+    assert_eq!(0, output(&graph, &operands(3, 0, 0b111)));
+
+    let mut graph = parse(entity(2).as_str(), None).unwrap();
+    graph.optimize();
+    assert_eq!(0, output(&graph, &operands(2, 0b11, 0)));
+    assert_eq!(1, output(&graph, &operands(2, 0, 0b01)));
+}
+
+#[test]
+fn unknown_bound() {
+    let content = "entity Test {
+  in_ports {
+    a : bits[2];
+  }
+  out_ports {
+    x : bits[2];
+  }
+  behaviour {
+    for i in 0..M {
+      (x[i]) <= a[i];
+    }
+  }
+}";
+
+    assert_eq!(
+        "error: `M` is not a valid number, expected a number, a parameter or a loop variable
+ --> test.mcl:9:17
+  |
+9 |     for i in 0..M {
+  |                 ^
+",
+        parse_err(content)
+    );
+}
+
+#[test]
+fn missing_range() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    for i in 2 {
+      (x) <= a;
+    }
+    (x) <= a;
+  }
+}";
+
+    assert_eq!(
+        "error: expected `..`, found `{`
+ --> test.mcl:9:16
+  |
+9 |     for i in 2 {
+  |                ^
+",
+        parse_err(content)
+    );
+}
@@ -0,0 +1,310 @@
+mod common;
+
+use std::collections::HashMap;
+
+use mclc::frontend::parse;
+
+use common::{parse_err, port_output};
+
+/// The Values of the Bits of the Input with the given Width
+fn input(name: &str, width: u32, value: u32) -> impl Iterator<Item = (String, bool)> + '_ {
+    (0..width).map(move |i| (format!("{}[{}]", name, i), value & (1 << i) != 0))
+}
+
+const ADDER: &str = "
+entity Adder<N: int> {
+  in_ports {
+    a : bits[N];
+    b : bits[N];
+  }
+  out_ports {
+    sum : bits[N];
+  }
+  behaviour {
+    (sum) <= a + b;
+  }
+}";
+
+#[test]
+fn different_widths() {
+    let content = format!(
+        "entity Test {{
+  in_ports {{
+    a : bits[2];
+    b : bits[3];
+  }}
+  out_ports {{
+    x : bits[2];
+    y : bits[3];
+  }}
+  behaviour {{
+    (x) <= Adder<2>(a, a);
+    (y) <= Adder<3>(b, Adder<3>(b, b));
+  }}
+}}
+{}",
+        ADDER
+    );
+
+    let mut graph = parse(content.as_str(), None).unwrap();
+    graph.optimize();
+
+    for a in 0..4 {
+        for b in 0..8 {
+            let inputs: HashMap<_, _> = input("a", 2, a).chain(input("b", 3, b)).collect();
+
+            assert_eq!((2 * a) % 4, port_output(&graph, "x", &inputs));
+            assert_eq!((3 * b) % 8, port_output(&graph, "y", &inputs));
+        }
+    }
+}
+
+#[test]
+fn forwarded_parameter() {
+    let content = format!(
+        "entity Test {{
+  in_ports {{
+    a : bits[2];
+  }}
+  out_ports {{
+    x : bits[2];
+    h : bit;
+  }}
+  behaviour {{
+    (x, h) <= Double<2>(a);
+  }}
+}}
+
+entity Double<W: int> {{
+  in_ports {{
+    v : bits[W];
+  }}
+  out_ports {{
+    res : bits[W];
+    high : bit;
+  }}
+  behaviour {{
+    (res) <= Adder<W>(v, v);
+    (high) <= v[W];
+  }}
+}}
+{}",
+        ADDER
+    );
+
+    assert_eq!(
+        "error: the selected bits are out of range for type `bits[2]`
+  --> test.mcl:24:15
+   |
+24 |     (high) <= v[W];
+   |               ^^^^
+",
+        parse_err(&content)
+    );
+
+    let content = content.replace("v[W]", "v[1]");
+    let mut graph = parse(content.as_str(), None).unwrap();
+    graph.optimize();
+
+    for a in 0..4 {
+        let inputs: HashMap<_, _> = input("a", 2, a).collect();
+        assert_eq!((2 * a) % 4, port_output(&graph, "x", &inputs));
+        assert_eq!(a >> 1, port_output(&graph, "h", &inputs));
+    }
+}
+
+#[test]
+fn parameter_count() {
+    let content = format!(
+        "entity Test {{
+  in_ports {{
+    a : bits[2];
+  }}
+  out_ports {{
+    x : bits[2];
+  }}
+  behaviour {{
+    (x) <= Adder(a, a);
+  }}
+}}
+{}",
+        ADDER
+    );
+
+    assert_eq!(
+        "error: `Adder` expects 1 parameters, but 0 were given
+ --> test.mcl:9:12
+  |
+9 |     (x) <= Adder(a, a);
+  |            ^^^^^
+",
+        parse_err(&content)
+    );
+}
+
+#[test]
+fn invalid_parameter() {
+    let content = format!(
+        "entity Test {{
+  in_ports {{
+    a : bits[2];
+  }}
+  out_ports {{
+    x : bits[2];
+  }}
+  behaviour {{
+    (x) <= Adder<M>(a, a);
+  }}
+}}
+{}",
+        ADDER
+    );
+
+    assert_eq!(
+        "error: `M` is not a valid parameter, expected a number or the name of a parameter
+ --> test.mcl:9:18
+  |
+9 |     (x) <= Adder<M>(a, a);
+  |                  ^
+",
+        parse_err(&content)
+    );
+}
+
+#[test]
+fn unknown_parameter_type() {
+    let content = "entity Test<N: bit> {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= a;
+  }
+}";
+
+    assert_eq!(
+        "error: unknown type `bit`
+ --> test.mcl:1:16
+  |
+1 | entity Test<N: bit> {
+  |                ^^^
+",
+        parse_err(content)
+    );
+}
+
+#[test]
+fn growing_recursion() {
+    let content = "entity Top {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= A<1>(a);
+  }
+}
+
+entity A<N: int> {
+  in_ports {
+    a : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    for i in N + 1..N + 2 {
+      (x) <= A<i>(a);
+    }
+  }
+}";
+
+    assert_eq!(
+        "error: the instance of `A` would be nested more than 256 instances deep, as generic entities keep being instantiated with new parameters
+  --> test.mcl:22:14
+   |
+22 |       (x) <= A<i>(a);
+   |              ^
+",
+        parse_err(content)
+    );
+}
+
+#[test]
+fn parameter_expression() {
+    let content = "entity Test {
+  in_ports {
+    a : bits[4];
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= Parity<4>(a);
+  }
+}
+
+entity Parity<N: int> {
+  in_ports {
+    a : bits[N];
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    if N > 2 {
+      (x) <= xor(a[N - 1], Parity<N - 1>(a[N - 2:0]));
+    } else {
+      (x) <= xor(a[1], a[0]);
+    }
+  }
+}";
+
+    let mut graph = parse(content, None).unwrap();
+    graph.optimize();
+
+    for a in 0..16 {
+        let inputs: HashMap<_, _> = input("a", 4, a).collect();
+        assert_eq!(a.count_ones() % 2, port_output(&graph, "x", &inputs));
+    }
+}
+
+#[test]
+fn width_expression() {
+    let content = "entity Test {
+  in_ports {
+    a : bits[3];
+  }
+  out_ports {
+    x : bits[4];
+  }
+  behaviour {
+    (x) <= Extend<3>(a);
+  }
+}
+
+entity Extend<N: int> {
+  in_ports {
+    v : bits[N];
+  }
+  out_ports {
+    wide : bits[N + 1];
+  }
+  behaviour {
+    (wide) <= concat(v[N - 1], v);
+  }
+}";
+
+    let mut graph = parse(content, None).unwrap();
+    graph.optimize();
+
+    for a in 0..8 {
+        let inputs: HashMap<_, _> = input("a", 3, a).collect();
+        assert_eq!(a | (a & 4) << 1, port_output(&graph, "x", &inputs));
+    }
+}
@@ -0,0 +1,162 @@
+mod common;
+
+use std::collections::HashMap;
+
+use mclc::frontend::parse;
+
+use common::{outputs, parse_err};
+
+#[test]
+fn mux() {
+    let content = "entity Test {
+  in_ports {
+    sel : bit;
+    a : bit;
+    b : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= mux(sel, a, b);
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    for value in 0..8 {
+        let inputs: HashMap<_, _> = [
+            ("sel", value & 1 != 0),
+            ("a", value & 2 != 0),
+            ("b", value & 4 != 0),
+        ]
+        .into_iter()
+        .collect();
+
+        let expected = if inputs["sel"] {
+            inputs["b"]
+        } else {
+            inputs["a"]
+        };
+        assert_eq!(vec![expected], outputs(&graph, &inputs));
+    }
+}
+
+#[test]
+fn mux4() {
+    let content = "entity Test {
+  in_ports {
+    sel : bits[2];
+    a : bit;
+    b : bit;
+    c : bit;
+    d : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= mux4(sel, a, b, c, d);
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    for sel in 0..4 {
+        for selected in [false, true] {
+            // Only the selected Input differs from all the others
+            let data = ["a", "b", "c", "d"]
+                .into_iter()
+                .enumerate()
+                .map(|(i, name)| (name, (i == sel) == selected));
+            let inputs: HashMap<_, _> = [("sel[0]", sel & 1 != 0), ("sel[1]", sel & 2 != 0)]
+                .into_iter()
+                .chain(data)
+                .collect();
+
+            assert_eq!(vec![selected], outputs(&graph, &inputs));
+        }
+    }
+}
+
+#[test]
+fn decode() {
+    let content = "entity Test {
+  in_ports {
+    a : bits[3];
+  }
+  out_ports {
+    x : bits[8];
+  }
+  behaviour {
+    (x) <= decode(a);
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    for value in 0..8 {
+        let inputs: HashMap<_, _> = [
+            ("a[0]", value & 1 != 0),
+            ("a[1]", value & 2 != 0),
+            ("a[2]", value & 4 != 0),
+        ]
+        .into_iter()
+        .collect();
+
+        let expected: Vec<_> = (0..8).map(|i| i == value).collect();
+        assert_eq!(expected, outputs(&graph, &inputs));
+    }
+}
+
+#[test]
+fn select_must_be_bit() {
+    let content = "entity Test {
+  in_ports {
+    sel : bits[2];
+    a : bit;
+    b : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= mux(sel, a, b);
+  }
+}";
+
+    assert_eq!(
+        "error: expected type `bit`, found `bits[2]`
+  --> test.mcl:11:16
+   |
+11 |     (x) <= mux(sel, a, b);
+   |                ^^^
+",
+        parse_err(content)
+    );
+}
+
+#[test]
+fn decode_too_wide() {
+    let content = "entity Test {
+  in_ports {
+    a : bits[9];
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= decode(a);
+  }
+}";
+
+    assert_eq!(
+        "error: `decode` supports at most 8 bits, found width 9
+ --> test.mcl:9:19
+  |
+9 |     (x) <= decode(a);
+  |                   ^
+",
+        parse_err(content)
+    );
+}
@@ -5,5 +5,5 @@ fn parse_2bitadder() {
     let result = mclc::frontend::parse(content, None);
     dbg!(&result);
 
-    todo!()
+    assert!(result.is_ok());
 }
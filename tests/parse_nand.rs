@@ -1,3 +1,5 @@
+use mclc::graph::normalized::{BuiltinOp, NodeType};
+
 #[test]
 fn parse_graph() {
     let content = "
@@ -21,5 +23,26 @@ entity NandGate {
     let result = mclc::frontend::parse(content, None);
 
     dbg!(&result);
-    todo!()
+    let graph = result.unwrap();
+
+    let ops: Vec<_> = graph
+        .nodes
+        .iter()
+        .filter_map(|n| match &n.inner {
+            NodeType::Operation { op } => Some(op.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(vec![BuiltinOp::And, BuiltinOp::Not], ops);
+}
+
+#[test]
+fn place_example() {
+    let content = include_str!("../examples/nandgate.mcl");
+
+    let mut graph = mclc::frontend::parse(content, None).unwrap();
+    graph.optimize();
+
+    let layout = mclc::backend::generate_layout(graph);
+    assert!(!layout.placement().place_commands().is_empty());
 }
@@ -0,0 +1,110 @@
+mod common;
+
+use mclc::{
+    backend::generate_layout,
+    frontend::{parse, Span},
+    graph::normalized::{BuiltinOp, Graph, NodeType},
+};
+
+use common::parse_source_err;
+
+fn entity(address_ty: &str, data_ty: &str, write_ty: &str) -> String {
+    format!(
+        "entity Test {{
+  in_ports {{
+    addr : {};
+    data : {};
+    we : {};
+    clk : bit;
+  }}
+  out_ports {{
+    x : {};
+  }}
+  behaviour {{
+    (x) <= ram(addr, data, we, clk);
+  }}
+}}",
+        address_ty, data_ty, write_ty, data_ty
+    )
+}
+
+/// Returns the Id of the single RAM in the Graph
+fn ram(graph: &Graph) -> (u32, BuiltinOp) {
+    let rams: Vec<_> = graph
+        .nodes
+        .iter()
+        .filter_map(|n| match &n.inner {
+            NodeType::Operation {
+                op: op @ BuiltinOp::Ram { .. },
+            } => Some((n.id, op.clone())),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(1, rams.len());
+    rams[0].clone()
+}
+
+#[test]
+fn single_node() {
+    let graph = parse(entity("bits[3]", "bits[4]", "bit").as_str(), None).unwrap();
+
+    let (id, op) = ram(&graph);
+    assert_eq!(
+        BuiltinOp::Ram {
+            address_width: 3,
+            data_width: 4,
+        },
+        op
+    );
+
+    let mut ports: Vec<_> = graph
+        .edges
+        .iter()
+        .filter(|e| e.dest_id == id)
+        .map(|e| e.dest_port)
+        .collect();
+    ports.sort_unstable();
+    assert_eq!((0..9).collect::<Vec<_>>(), ports);
+}
+
+#[test]
+fn write_enable_is_a_bit() {
+    let source = Span::with_name("test.mcl", entity("bits[3]", "bits[4]", "bits[2]"));
+
+    assert_eq!(
+        "error: expected type `bit`, found `bits[2]`
+  --> test.mcl:12:28
+   |
+12 |     (x) <= ram(addr, data, we, clk);
+   |                            ^^
+",
+        parse_source_err(source)
+    );
+}
+
+#[test]
+fn address_too_wide() {
+    let source = Span::with_name("test.mcl", entity("bits[7]", "bits[4]", "bit"));
+
+    assert_eq!(
+        "error: `ram` supports at most 6 address bits, found width 7
+  --> test.mcl:12:16
+   |
+12 |     (x) <= ram(addr, data, we, clk);
+   |                ^^^^
+",
+        parse_source_err(source)
+    );
+}
+
+#[test]
+fn place_ram() {
+    let mut graph = parse(entity("bit", "bits[2]", "bit").as_str(), None).unwrap();
+    graph.optimize();
+
+    let layout = generate_layout(graph);
+
+    let commands = layout.placement().place_commands();
+    assert!(commands.iter().any(|c| c.contains("redstone_wall_torch")));
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use std::path::PathBuf;
+
+use mclc::{
+    backend::generate_layout,
+    frontend::{parse, Span},
+    graph::normalized::{BuiltinOp, Graph, NodeType},
+};
+
+use common::parse_source_err;
+
+/// Writes the Content into a File in its own temporary Directory, returning the Path of the File
+fn rom_file(name: &str, content: &[u8]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mclc-rom-{}", name));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join(name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn entity(address_ty: &str, data_ty: &str, value: &str) -> String {
+    format!(
+        "entity Test {{
+  in_ports {{
+    a : {};
+  }}
+  out_ports {{
+    x : {};
+  }}
+  behaviour {{
+    (x) <= {};
+  }}
+}}",
+        address_ty, data_ty, value
+    )
+}
+
+/// Returns the single ROM in the Graph
+fn rom(graph: &Graph) -> BuiltinOp {
+    let roms: Vec<_> = graph
+        .nodes
+        .iter()
+        .filter_map(|n| match &n.inner {
+            NodeType::Operation {
+                op: op @ BuiltinOp::Rom { .. },
+            } => Some(op.clone()),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(1, roms.len());
+    roms[0].clone()
+}
+
+#[test]
+fn hex_file() {
+    let path = rom_file("words.hex", b"01 02 // the first two words\n3 ff\n");
+    let value = format!("rom(\"{}\", a)", path.display());
+
+    let graph = parse(entity("bits[2]", "bits[8]", &value).as_str(), None).unwrap();
+
+    assert_eq!(
+        BuiltinOp::Rom {
+            address_width: 2,
+            data_width: 8,
+            words: vec![1, 2, 3, 255],
+        },
+        rom(&graph)
+    );
+}
+
+#[test]
+fn bin_file() {
+    let path = rom_file("bytes.bin", &[7, 0, 42]);
+    let value = format!("rom(\"{}\", a)", path.display());
+
+    let graph = parse(entity("bits[2]", "bits[8]", &value).as_str(), None).unwrap();
+
+    assert_eq!(
+        BuiltinOp::Rom {
+            address_width: 2,
+            data_width: 8,
+            words: vec![7, 0, 42],
+        },
+        rom(&graph)
+    );
+}
+
+#[test]
+fn relative_to_source() {
+    let path = rom_file("relative.hex", b"a 5");
+    let source = Span::with_name(
+        path.with_file_name("main.mcl").display().to_string(),
+        entity("bit", "bits[4]", "rom(\"relative.hex\", a)"),
+    );
+
+    let graph = parse(source, None).unwrap();
+
+    assert_eq!(
+        BuiltinOp::Rom {
+            address_width: 1,
+            data_width: 4,
+            words: vec![10, 5],
+        },
+        rom(&graph)
+    );
+}
+
+#[test]
+fn too_many_words() {
+    let path = rom_file("large.hex", b"1 2 3");
+    let source = Span::with_name(
+        path.with_file_name("main.mcl").display().to_string(),
+        entity("bit", "bits[4]", "rom(\"large.hex\", a)"),
+    );
+
+    assert_eq!(
+        format!(
+            "error: the file contains 3 words, but 1 address bits can only select 2
+ --> {}:9:16
+  |
+9 |     (x) <= rom(\"large.hex\", a);
+  |                ^^^^^^^^^^^
+",
+            path.with_file_name("main.mcl").display()
+        ),
+        parse_source_err(source)
+    );
+}
+
+#[test]
+fn unknown_file_type() {
+    let source = Span::with_name("test.mcl", entity("bit", "bit", "rom(\"data.txt\", a)"));
+
+    assert_eq!(
+        "error: could not load \"data.txt\": expected a `.hex` or `.bin` file
+ --> test.mcl:9:16
+  |
+9 |     (x) <= rom(\"data.txt\", a);
+  |                ^^^^^^^^^^
+",
+        parse_source_err(source)
+    );
+}
+
+#[test]
+fn misplaced_text() {
+    let source = Span::with_name("test.mcl", entity("bit", "bit", "!\"data.hex\""));
+
+    assert_eq!(
+        "error: a file name is only allowed as the first argument of `rom`
+ --> test.mcl:9:13
+  |
+9 |     (x) <= !\"data.hex\";
+  |             ^^^^^^^^^^
+",
+        parse_source_err(source)
+    );
+}
+
+#[test]
+fn place_rom() {
+    let path = rom_file("place.hex", b"1 0 2 3");
+    let value = format!("rom(\"{}\", a)", path.display());
+
+    let mut graph = parse(entity("bits[2]", "bits[4]", &value).as_str(), None).unwrap();
+    graph.optimize();
+
+    let layout = generate_layout(graph);
+
+    let commands = layout.placement().place_commands();
+    assert!(commands.iter().any(|c| c.contains("redstone_torch")));
+}
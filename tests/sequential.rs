@@ -0,0 +1,140 @@
+mod common;
+
+use mclc::{
+    backend::generate_layout,
+    frontend::parse,
+    graph::normalized::{BuiltinOp, NodeType},
+};
+
+use common::parse_err;
+
+const CONTENT: &str = "entity Storage {
+  in_ports {
+    d : bit;
+    clk : bit;
+    s : bit;
+    r : bit;
+  }
+  out_ports {
+    x : bit;
+    y : bit;
+    z : bit;
+  }
+  behaviour {
+    (x) <= dff(d, clk);
+    (y) <= latch(d, clk);
+    (z) <= rs_latch(s, r);
+  }
+}";
+
+#[test]
+fn storage_elements() {
+    let graph = parse(CONTENT, None).unwrap();
+
+    let ops: Vec<_> = graph
+        .nodes
+        .iter()
+        .filter_map(|n| match &n.inner {
+            NodeType::Operation { op } => Some(op.clone()),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        vec![BuiltinOp::Dff, BuiltinOp::Latch, BuiltinOp::RsLatch],
+        ops
+    );
+}
+
+#[test]
+fn register_shares_clock() {
+    let content = "entity Register {
+  in_ports {
+    d : bits[4];
+    clk : bit;
+  }
+  out_ports {
+    q : bits[4];
+  }
+  behaviour {
+    (q) <= dff(d, clk);
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    let dffs: Vec<_> = graph
+        .nodes
+        .iter()
+        .filter(|n| matches!(n.inner, NodeType::Operation { op: BuiltinOp::Dff }))
+        .collect();
+    assert_eq!(4, dffs.len());
+
+    let clock = graph
+        .nodes
+        .iter()
+        .find(|n| matches!(&n.inner, NodeType::Input { name, .. } if name == "clk"))
+        .unwrap();
+    let clock_fanout: usize = dffs
+        .iter()
+        .map(|n| {
+            graph
+                .edges_to_node(n.id)
+                .iter()
+                .filter(|e| e.dest_port == 1)
+                .count()
+        })
+        .sum();
+    assert_eq!(4, clock_fanout);
+    assert!(!graph.edges_from_node(clock.id).is_empty());
+}
+
+#[test]
+fn clock_must_be_bit() {
+    let content = "entity Register {
+  in_ports {
+    d : bits[4];
+    clk : bits[4];
+  }
+  out_ports {
+    q : bits[4];
+  }
+  behaviour {
+    (q) <= dff(d, clk);
+  }
+}";
+
+    let err = parse_err(content);
+    assert!(err.contains("bits[4]"), "{}", err);
+}
+
+#[test]
+fn place_storage_elements() {
+    for op in ["dff", "latch", "rs_latch"] {
+        let content = format!(
+            "entity Storage {{
+  in_ports {{
+    a : bit;
+    b : bit;
+  }}
+  out_ports {{
+    x : bit;
+  }}
+  behaviour {{
+    (x) <= {}(a, b);
+  }}
+}}",
+            op
+        );
+
+        let mut graph = parse(content.as_str(), None).unwrap();
+        graph.optimize();
+
+        let layout = generate_layout(graph);
+
+        let commands = layout.placement().place_commands();
+        assert!(commands
+            .iter()
+            .any(|c| c.contains("repeater[facing=north]")));
+    }
+}
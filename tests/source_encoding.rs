@@ -0,0 +1,40 @@
+#[test]
+fn windows_line_endings_and_tabs() {
+    let content = "entity NotGate {\r\n\tin_ports {\r\n\t\tsrc : bit;\r\n\t}\r\n\r\n\tout_ports {\r\n\t\tresult : bit;\r\n\t}\r\n\r\n\tbehaviour {\r\n\t\t(result) <= or(src, src);\r\n\t}\r\n}";
+
+    let result = mclc::frontend::parse(content, None);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn non_ascii_names() {
+    let content = "
+/// Prüft, ob beide Eingänge gesetzt sind
+entity UndGatter {
+  in_ports {
+    eingang_ä : bit;
+    eingang_ö : bit;
+  }
+
+  out_ports {
+    ergebnis_ü : bit;
+  }
+
+  behaviour {
+    /* Zwischenergebnis ß */
+    (zwischen_ß) = and(eingang_ä, eingang_ö);
+    (ergebnis_ü) <= (zwischen_ß);
+  }
+}";
+
+    let docs = mclc::frontend::documentation(content).unwrap();
+    assert_eq!("UndGatter", docs[0].name);
+    assert_eq!(
+        Some("Prüft, ob beide Eingänge gesetzt sind"),
+        docs[0].doc.as_deref()
+    );
+
+    let result = mclc::frontend::parse(content, Some("UndGatter".to_string()));
+    assert!(result.is_ok());
+}
@@ -0,0 +1,231 @@
+mod common;
+
+use std::collections::HashMap;
+
+use mclc::{
+    frontend::parse,
+    graph::normalized::{BuiltinOp, Graph, NodeType},
+};
+
+use common::{outputs, parse_err};
+
+fn operations(graph: &Graph) -> Vec<BuiltinOp> {
+    graph
+        .nodes
+        .iter()
+        .filter_map(|n| match &n.inner {
+            NodeType::Operation { op } => Some(op.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn full_table() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+    b : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    table (a, b) -> (x) {
+      0 0 => 0;
+      0 1 => 1;
+      1 0 => 1;
+      1 1 => 0;
+    }
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    for value in 0..4 {
+        let inputs: HashMap<_, _> = [("a", value & 1 != 0), ("b", value & 2 != 0)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(vec![inputs["a"] ^ inputs["b"]], outputs(&graph, &inputs));
+    }
+}
+
+#[test]
+fn dont_care_inputs() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+    b : bit;
+    c : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    table (a, b, c) -> (x) {
+      1 - - => 1;
+      0 1 - => 1;
+    }
+  }
+}";
+
+    let mut graph = parse(content, None).unwrap();
+    graph.optimize();
+
+    // The Table is simply `a | b`
+    assert_eq!(vec![BuiltinOp::Or], operations(&graph));
+}
+
+#[test]
+fn dont_care_outputs() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+    b : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    table (a, b) -> (x) {
+      0 0 => 0;
+      0 1 => -;
+      1 0 => 1;
+      1 1 => 1;
+    }
+  }
+}";
+
+    let mut graph = parse(content, None).unwrap();
+    graph.optimize();
+
+    // The Table is simply `a`, using the Don't-Care as a 0
+    assert_eq!(Vec::<BuiltinOp>::new(), operations(&graph));
+}
+
+#[test]
+fn buses() {
+    let content = "entity Test {
+  in_ports {
+    n : bits[2];
+  }
+  out_ports {
+    next : bits[2];
+    zero : bit;
+  }
+  behaviour {
+    table (n) -> (next, zero) {
+      00 => 01 1;
+      01 => 10 0;
+      10 => 11 0;
+      11 => 00 0;
+    }
+  }
+}";
+
+    let graph = parse(content, None).unwrap();
+
+    for n in 0..4 {
+        let inputs: HashMap<_, _> = [("n[0]", n & 1 != 0), ("n[1]", n & 2 != 0)]
+            .into_iter()
+            .collect();
+
+        let next = (n + 1) % 4;
+        assert_eq!(
+            vec![next & 1 != 0, next & 2 != 0, n == 0],
+            outputs(&graph, &inputs)
+        );
+    }
+}
+
+#[test]
+fn conflicting_rows() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+    b : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    table (a, b) -> (x) {
+      1 - => 1;
+      - 0 => 0;
+    }
+  }
+}";
+
+    assert_eq!(
+        "error: the row produces different outputs than an earlier row for the same inputs
+  --> test.mcl:12:7
+   |
+12 |       - 0 => 0;
+   |       ^^^^^^^^^
+
+error: the earlier row is here
+  --> test.mcl:11:7
+   |
+11 |       1 - => 1;
+   |       ^^^^^^^^^
+",
+        parse_err(content)
+    );
+}
+
+#[test]
+fn invalid_cell() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+    b : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    table (a, b) -> (x) {
+      1x => 1;
+    }
+  }
+}";
+
+    assert_eq!(
+        "error: expected `0`, `1` or `-` in the table, found `x`
+  --> test.mcl:11:8
+   |
+11 |       1x => 1;
+   |        ^
+",
+        parse_err(content)
+    );
+}
+
+#[test]
+fn row_width() {
+    let content = "entity Test {
+  in_ports {
+    a : bit;
+    b : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    table (a, b) -> (x) {
+      1 0 1 => 1;
+    }
+  }
+}";
+
+    assert_eq!(
+        "error: expected 2 values in the row, found 3
+  --> test.mcl:11:7
+   |
+11 |       1 0 1 => 1;
+   |       ^^^^^
+",
+        parse_err(content)
+    );
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use mclc::frontend::{parse, Span};
+
+use common::parse_source_err;
+
+const GATES: &str = "entity MyAnd {
+  in_ports {
+    a : bit;
+    b : bit;
+  }
+  out_ports {
+    x : bit;
+  }
+  behaviour {
+    (x) <= and(a, b);
+  }
+}
+";
+
+fn my_and_user(name: &str) -> String {
+    format!(
+        "entity {} {{
+  in_ports {{
+    a : bit;
+    b : bit;
+  }}
+  out_ports {{
+    x : bit;
+  }}
+  behaviour {{
+    (x) <= MyAnd(a, b);
+  }}
+}}
+",
+        name
+    )
+}
+
+/// Writes the Files into their own temporary Directory, returning the Path of the Directory
+fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mclc-use-{}", name));
+    for (path, content) in files {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+fn main_source(dir: &Path, content: String) -> Span {
+    Span::with_name(dir.join("main.mcl").display().to_string(), content)
+}
+
+#[test]
+fn entity_from_other_file() {
+    let dir = project("other_file", &[("lib/gates.mcl", GATES)]);
+    let content = format!("use \"lib/gates.mcl\";\n\n{}", my_and_user("Test"));
+
+    assert!(parse(main_source(&dir, content), None).is_ok());
+}
+
+#[test]
+fn nested_and_repeated_uses() {
+    let wrapper = format!("use \"gates.mcl\";\n{}", my_and_user("Wrapper"));
+    let dir = project(
+        "nested",
+        &[("lib/gates.mcl", GATES), ("lib/wrapper.mcl", &wrapper)],
+    );
+
+    // The Gates are used twice, once directly and once through the Wrapper
+    let content = format!(
+        "use \"lib/gates.mcl\";\nuse \"lib/wrapper.mcl\";\n\n{}",
+        my_and_user("Test").replace("MyAnd", "Wrapper")
+    );
+
+    assert!(parse(main_source(&dir, content), None).is_ok());
+}
+
+#[test]
+fn duplicate_across_files() {
+    let dir = project("duplicate", &[("gates.mcl", GATES)]);
+    let content = format!("use \"gates.mcl\";\n\n{}", GATES);
+
+    assert_eq!(
+        format!(
+            "error: the entity `MyAnd` is defined multiple times
+ --> {}:1:8
+  |
+1 | entity MyAnd {{
+  |        ^^^^^
+
+error: the first definition is here
+ --> {}:3:8
+  |
+3 | entity MyAnd {{
+  |        ^^^^^
+",
+            dir.join("gates.mcl").display(),
+            dir.join("main.mcl").display()
+        ),
+        parse_source_err(main_source(&dir, content))
+    );
+}
+
+#[test]
+fn missing_file() {
+    let source = Span::with_name("test.mcl", "use \"missing.mcl\";\n");
+
+    let err = parse_source_err(source);
+    assert!(err.starts_with("error: could not read \"missing.mcl\": "));
+    assert!(err.contains(" --> test.mcl:1:5\n"));
+}
+
+#[test]
+fn syntax_error_in_used_file() {
+    let dir = project(
+        "syntax_error",
+        &[("broken.mcl", "entity Broken {\n  in_ports\n}\n")],
+    );
+    let content = format!("use \"broken.mcl\";\n\n{}", GATES);
+
+    assert_eq!(
+        format!(
+            "error: expected `{{`, found the end of the block
+ --> {}:3:1
+  |
+3 | }}
+  | ^
+",
+            dir.join("broken.mcl").display()
+        ),
+        parse_source_err(main_source(&dir, content))
+    );
+}